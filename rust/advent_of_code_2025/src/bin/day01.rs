@@ -68,7 +68,7 @@ fn main() {
 
     let rotations: Vec<i16> = input
         .lines()
-        .map(|rotation_str| parse_rotation(rotation_str))
+        .map(parse_rotation)
         .collect();
 
     let mut position = INITIAL_POSITION;
@@ -82,7 +82,7 @@ fn repeated_multiplier(base: u128, repeat_count: usize) -> u128 {
 /// - proper_divisors(6) = [1, 2, 3]
 /// - proper_divisors(12) = [1, 2, 3, 4, 6]
 fn proper_divisors(n: usize) -> Vec<usize> {
-    (1..n).filter(|&i| n % i == 0).collect()
+    (1..n).filter(|&i| n.is_multiple_of(i)).collect()
 }
 
 fn parse_ranges(input: &str) -> Vec<(u128, u128)> {
@@ -44,9 +44,9 @@ fn max_joltage_for_line(line: &str, num_digits: usize) -> u128 {
         // Find leftmost occurrence of maximum digit in [left, right]
         let mut best_pos = left;
         let mut best_digit = digits[left];
-        for i in (left + 1)..=right {
-            if digits[i] > best_digit {
-                best_digit = digits[i];
+        for (i, &digit) in digits.iter().enumerate().take(right + 1).skip(left + 1) {
+            if digit > best_digit {
+                best_digit = digit;
                 best_pos = i;
             }
         }
@@ -1,51 +1,18 @@
-fn part1(input: &str) -> i64 {
-    // Parse the worksheet into rows
-    let lines: Vec<&str> = input.lines().collect();
-
-    // Split each line by whitespace to get values
-    let rows: Vec<Vec<&str>> = lines
-        .iter()
-        .map(|line| line.split_whitespace().collect())
-        .collect();
-
-    // Transpose to get problems (columns)
-    let num_problems = rows.iter().map(|r| r.len()).max().unwrap_or(0);
-    let mut grand_total = 0i64;
-
-    for col in 0..num_problems {
-        // Extract values for this problem
-        let mut values: Vec<&str> = Vec::new();
-        for row in &rows {
-            // Will assert if col is bad, but we can assume problem is well formed.
-            values.push(row[col]);
-        }
-
-        // Last value is the operator
-        let operator = values.last().unwrap();
-        let numbers: Vec<i64> = values[..values.len()-1]
-            .iter()
-            .map(|s| s.parse().unwrap())
-            .collect();
-
-        // Calculate result based on operator
-        let result: i64 = match *operator {
-            "*" => numbers.iter().copied().product(),
-            "+" => numbers.iter().copied().sum(),
-            _ => panic!("Unknown operator: {}", operator),
-        };
-
-        grand_total += result;
-    }
-
-    grand_total
+/// A single problem on the worksheet: a block of columns bounded by blank
+/// separator columns, with the operator found somewhere in the block.
+struct Problem {
+    // Inclusive start and exclusive end column of the block.
+    start_col: usize,
+    end_col: usize,
+    operator: char,
 }
 
-fn part2(input: &str) -> i64 {
-    // Parse into character grid
+/// Parses the worksheet into a character grid, padding short lines with spaces.
+fn parse_grid(input: &str) -> Vec<Vec<char>> {
     let lines: Vec<&str> = input.lines().collect();
     let max_len = lines.iter().map(|l| l.len()).max().unwrap_or(0);
 
-    let grid: Vec<Vec<char>> = lines
+    lines
         .iter()
         .map(|line| {
             let mut chars: Vec<char> = line.chars().collect();
@@ -53,79 +20,151 @@ fn part2(input: &str) -> i64 {
             chars.resize(max_len, ' ');
             chars
         })
-        .collect();
-
-    let mut grand_total = 0i64;
-    let mut current_numbers: Vec<i64> = Vec::new();
-    let mut current_operator: Option<char> = None;
-
-    // Read columns from right to left
-    let mut col = max_len;
-    while col > 0 {
-        col -= 1;
-
-        // Read this column top to bottom, collecting digits and operator
-        let mut digits = String::new();
-        let mut operator: Option<char> = None;
-
-        for row in 0..grid.len() {
-            let ch = grid[row][col];
-            if ch.is_ascii_digit() {
-                digits.push(ch);
-            } else if ch == '*' || ch == '+' {
-                operator = Some(ch);
-            }
+        .collect()
+}
+
+/// Splits the grid into problem blocks, left to right.
+///
+/// A column is a separator if every row holds a space there. Each run of
+/// non-separator columns is one problem.
+fn find_problems(grid: &[Vec<char>]) -> Vec<Problem> {
+    let width = grid.first().map_or(0, |row| row.len());
+    let is_blank = |col: usize| grid.iter().all(|row| row[col] == ' ');
+
+    let mut problems = Vec::new();
+    let mut col = 0;
+    while col < width {
+        if is_blank(col) {
+            col += 1;
+            continue;
         }
 
-        // Check if this column is blank (separator)
-        if digits.is_empty() && operator.is_none() {
-            // Blank separator column - if we have accumulated numbers, calculate the problem
-            if !current_numbers.is_empty() {
-                if let Some(op) = current_operator {
-                    let result: i64 = match op {
-                        '*' => current_numbers.iter().copied().product(),
-                        '+' => current_numbers.iter().copied().sum(),
-                        _ => 0,
-                    };
-                    grand_total += result;
-                }
-                current_numbers.clear();
-                current_operator = None;
-            } else { 
-                unreachable!("Blank separator column with no numbers to process");
-            }
-        } else {
-            // This column has content
-            if !digits.is_empty() {
-                let number: i64 = digits.parse().unwrap();
-                current_numbers.push(number);
-            }
-            if operator.is_some() {
-                current_operator = operator;
-            }
+        let start_col = col;
+        while col < width && !is_blank(col) {
+            col += 1;
         }
+
+        let operator = grid
+            .iter()
+            .flat_map(|row| &row[start_col..col])
+            .copied()
+            .find(|&ch| ch == '*' || ch == '+')
+            .unwrap_or_else(|| panic!("No operator in columns {}..{}", start_col, col));
+
+        problems.push(Problem {
+            start_col,
+            end_col: col,
+            operator,
+        });
     }
 
-    // Handle the last problem (leftmost)
-    if !current_numbers.is_empty() {
-        if let Some(op) = current_operator {
-            let result: i64 = match op {
-                '*' => current_numbers.iter().copied().product(),
-                '+' => current_numbers.iter().copied().sum(),
-                _ => 0,
-            };
-            grand_total += result;
-        }
-    } else {
-        unreachable!("Should have problem to process after reading leftmost column");
+    problems
+}
+
+/// Reads the operands of a problem the part 1 way: one number per row.
+fn row_operands(grid: &[Vec<char>], problem: &Problem) -> Vec<i64> {
+    grid.iter()
+        .filter_map(|row| {
+            let digits: String = row[problem.start_col..problem.end_col]
+                .iter()
+                .filter(|ch| ch.is_ascii_digit())
+                .collect();
+            (!digits.is_empty()).then(|| digits.parse().unwrap())
+        })
+        .collect()
+}
+
+/// Reads the operands of a problem the part 2 way: one number per column,
+/// top to bottom, reading columns from right to left.
+fn column_operands(grid: &[Vec<char>], problem: &Problem) -> Vec<i64> {
+    (problem.start_col..problem.end_col)
+        .rev()
+        .filter_map(|col| {
+            let digits: String = grid
+                .iter()
+                .map(|row| row[col])
+                .filter(|ch| ch.is_ascii_digit())
+                .collect();
+            (!digits.is_empty()).then(|| digits.parse().unwrap())
+        })
+        .collect()
+}
+
+fn evaluate(operator: char, numbers: &[i64]) -> i64 {
+    match operator {
+        '*' => numbers.iter().copied().product(),
+        '+' => numbers.iter().copied().sum(),
+        _ => panic!("Unknown operator: {}", operator),
     }
+}
+
+fn part1(input: &str) -> i64 {
+    let grid = parse_grid(input);
 
-    grand_total
+    find_problems(&grid)
+        .iter()
+        .map(|problem| evaluate(problem.operator, &row_operands(&grid, problem)))
+        .sum()
+}
+
+fn part2(input: &str) -> i64 {
+    let grid = parse_grid(input);
+
+    find_problems(&grid)
+        .iter()
+        .map(|problem| evaluate(problem.operator, &column_operands(&grid, problem)))
+        .sum()
+}
+
+/// Formats an evaluated problem, e.g. `123 * 45 * 6 = 33210`.
+fn describe(operator: char, numbers: &[i64]) -> String {
+    let operands: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+    format!(
+        "{} = {}",
+        operands.join(&format!(" {} ", operator)),
+        evaluate(operator, numbers)
+    )
+}
+
+/// Prints every problem block next to how part 1 and part 2 read it, so a
+/// wrong answer can be traced back to the block that was misread.
+fn explain(input: &str) {
+    let grid = parse_grid(input);
+
+    for (index, problem) in find_problems(&grid).iter().enumerate() {
+        let annotations = [
+            format!(
+                "problem {}: columns {}..{}, operator '{}'",
+                index + 1,
+                problem.start_col,
+                problem.end_col,
+                problem.operator
+            ),
+            format!("part 1: {}", describe(problem.operator, &row_operands(&grid, problem))),
+            format!("part 2: {}", describe(problem.operator, &column_operands(&grid, problem))),
+        ];
+
+        let block_width = problem.end_col - problem.start_col;
+        let height = grid.len().max(annotations.len());
+        for line in 0..height {
+            let block: String = grid
+                .get(line)
+                .map(|row| row[problem.start_col..problem.end_col].iter().collect())
+                .unwrap_or_default();
+            let annotation = annotations.get(line).map_or("", |a| a.as_str());
+            println!("{:<width$} | {}", block, annotation, width = block_width);
+        }
+        println!();
+    }
 }
 
 fn main() {
     let input = include_str!("../../../../inputs/day06.txt");
 
-    println!("{}", part1(&input));
-    println!("{}", part2(&input));
-}
\ No newline at end of file
+    if std::env::args().any(|arg| arg == "--explain") {
+        explain(input);
+    }
+
+    println!("{}", part1(input));
+    println!("{}", part2(input));
+}
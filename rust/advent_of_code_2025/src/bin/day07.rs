@@ -1,5 +1,8 @@
-use advent_of_code_2025::in_bounds;
+use advent_of_code_2025::counting::{CountMode, CountOverflow, Counting, WithCounting};
+use advent_of_code_2025::option_value;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
 
 fn parse_grid(input: &str) -> Vec<Vec<char>> {
     input.lines().map(|line| line.chars().collect()).collect()
@@ -16,125 +19,623 @@ fn find_start(grid: &[Vec<char>]) -> (usize, usize) {
     panic!("Start position not found in grid");
 }
 
-fn part1(input: &str) -> i64 {
-    let grid = parse_grid(input);
-    let (start_row, start_col) = find_start(&grid);
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn delta(self) -> (i64, i64) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
+    /// The two directions perpendicular to this one, ordered so the first is
+    /// the left/upper side on screen and the second the right/lower side.
+    fn sides(self) -> (Direction, Direction) {
+        match self {
+            Direction::Up | Direction::Down => (Direction::Left, Direction::Right),
+            Direction::Left | Direction::Right => (Direction::Up, Direction::Down),
+        }
+    }
+
+    /// Direction after bouncing off a `/` mirror.
+    fn reflect_forward(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    /// Direction after bouncing off a `\` mirror.
+    fn reflect_back(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Down,
+        }
+    }
+}
+
+/// What happens to a beam that would leave the manifold.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum EdgeBehaviour {
+    /// The beam leaves the manifold; its timeline is complete.
+    Exit,
+    /// The beam re-enters from the opposite edge.
+    Wrap,
+    /// Leaving through this edge is a malformed manifold.
+    Error,
+}
+
+impl EdgeBehaviour {
+    fn parse(name: &str) -> EdgeBehaviour {
+        match name {
+            "exit" => EdgeBehaviour::Exit,
+            "wrap" => EdgeBehaviour::Wrap,
+            "error" => EdgeBehaviour::Error,
+            _ => panic!("Unknown edge behaviour: {}", name),
+        }
+    }
+}
+
+/// Edge behaviour for each side of the manifold, keyed by the direction a
+/// beam is travelling when it crosses that side.
+#[derive(Clone, Copy, Debug)]
+struct BeamConfig {
+    top: EdgeBehaviour,
+    bottom: EdgeBehaviour,
+    left: EdgeBehaviour,
+    right: EdgeBehaviour,
+}
+
+impl BeamConfig {
+    /// The puzzle's rules: beams only ever leave through the bottom, and a
+    /// split that pushes a beam off either side is a malformed manifold.
+    fn puzzle() -> Self {
+        BeamConfig {
+            top: EdgeBehaviour::Error,
+            bottom: EdgeBehaviour::Exit,
+            left: EdgeBehaviour::Error,
+            right: EdgeBehaviour::Error,
+        }
+    }
+
+    /// Parses `top,bottom,left,right`, e.g. `error,exit,wrap,wrap`.
+    fn parse(spec: &str) -> Self {
+        let edges: Vec<EdgeBehaviour> = spec.split(',').map(EdgeBehaviour::parse).collect();
+        assert_eq!(edges.len(), 4, "Expected four edge behaviours: top,bottom,left,right");
+        BeamConfig {
+            top: edges[0],
+            bottom: edges[1],
+            left: edges[2],
+            right: edges[3],
+        }
+    }
+
+    fn edge(&self, direction: Direction) -> EdgeBehaviour {
+        match direction {
+            Direction::Up => self.top,
+            Direction::Down => self.bottom,
+            Direction::Left => self.left,
+            Direction::Right => self.right,
+        }
+    }
+}
+
+/// A beam sitting on a cell, about to move in `direction`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Beam {
+    row: usize,
+    col: usize,
+    direction: Direction,
+    /// Set for a beam a splitter has just put beside itself, which hasn't met the
+    /// cell's tile yet.
+    entering: bool,
+}
+
+#[derive(Debug)]
+enum BeamError {
+    OffGrid { row: i64, col: i64, direction: Direction },
+    UnknownTile { row: usize, col: usize, tile: char },
+    Loop { beam: Beam },
+    Overflow,
+}
+
+impl From<CountOverflow> for BeamError {
+    fn from(_: CountOverflow) -> Self {
+        BeamError::Overflow
+    }
+}
+
+impl fmt::Display for BeamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BeamError::OffGrid { row, col, direction } => {
+                write!(f, "Beam moving {:?} left the manifold at ({}, {})", direction, row, col)
+            }
+            BeamError::UnknownTile { row, col, tile } => {
+                write!(f, "Unknown tile '{}' at ({}, {})", tile, row, col)
+            }
+            BeamError::Loop { beam } => write!(
+                f,
+                "Beam at ({}, {}) moving {:?} loops forever, timelines are unbounded",
+                beam.row, beam.col, beam.direction
+            ),
+            BeamError::Overflow => {
+                write!(f, "Timeline count overflowed, try --count big or --count mod:<p>")
+            }
+        }
+    }
+}
+
+/// The outcome of advancing one beam by a single cell.
+struct Step {
+    /// The cell the beam landed on, or `None` if it left through an `Exit` edge.
+    landed: Option<(usize, usize)>,
+    /// Beams continuing from this step (empty if absorbed or exited).
+    beams: Vec<Beam>,
+    /// Beams that left through an `Exit` edge during this step.
+    exits: i64,
+}
+
+/// Everything reached by following each distinct beam state once.
+struct Trace {
+    splits: i64,
+    // Cells a beam passed through, excluding splitters.
+    beam_cells: HashSet<(usize, usize)>,
+    splitters_hit: HashSet<(usize, usize)>,
+}
+
+/// Timeline counts, in total and for each cell a beam passes through.
+struct Timelines<V> {
+    total: V,
+    per_cell: HashMap<(usize, usize), V>,
+}
+
+struct Manifold<'a> {
+    grid: &'a [Vec<char>],
+    config: BeamConfig,
+}
+
+impl Manifold<'_> {
+    fn rows(&self) -> i64 {
+        self.grid.len() as i64
+    }
+
+    fn cols(&self) -> i64 {
+        self.grid[0].len() as i64
+    }
+
+    /// Moves from `(row, col)` one cell in `direction`, applying the edge
+    /// behaviour if that leaves the grid. Returns `None` if the beam exited.
+    fn offset(
+        &self,
+        row: usize,
+        col: usize,
+        direction: Direction,
+    ) -> Result<Option<(usize, usize)>, BeamError> {
+        let (d_row, d_col) = direction.delta();
+        let new_row = row as i64 + d_row;
+        let new_col = col as i64 + d_col;
+
+        if (0..self.rows()).contains(&new_row) && (0..self.cols()).contains(&new_col) {
+            return Ok(Some((new_row as usize, new_col as usize)));
+        }
+
+        match self.config.edge(direction) {
+            EdgeBehaviour::Exit => Ok(None),
+            EdgeBehaviour::Wrap => Ok(Some((
+                new_row.rem_euclid(self.rows()) as usize,
+                new_col.rem_euclid(self.cols()) as usize,
+            ))),
+            EdgeBehaviour::Error => Err(BeamError::OffGrid {
+                row: new_row,
+                col: new_col,
+                direction,
+            }),
+        }
+    }
+
+    /// Emits beams entering the cells beside `(row, col)`, one per side, still
+    /// travelling in `direction`. Sides that push a beam out through an `Exit`
+    /// edge count as exits instead.
+    fn emit_sides(
+        &self,
+        step: &mut Step,
+        (row, col): (usize, usize),
+        direction: Direction,
+        sides: &[Direction],
+    ) -> Result<(), BeamError> {
+        for &side in sides {
+            match self.offset(row, col, side)? {
+                Some((row, col)) => {
+                    step.beams.push(Beam { row, col, direction, entering: true })
+                }
+                None => step.exits += 1,
+            }
+        }
+        Ok(())
+    }
 
-    let mut splits = 0;
-    let mut beams: HashSet<(usize, usize)> = HashSet::new();
-    beams.insert((start_row, start_col));
+    /// Advances a beam one cell and applies the tile it lands on. A beam that is
+    /// still entering its cell stays put and has that cell's tile applied instead.
+    ///
+    /// Tiles:
+    /// * `.` and `S` - empty, the beam carries on.
+    /// * `^` - splitter, the beam is replaced by two beams travelling the same
+    ///   way into the cells either side of the splitter, where those cells'
+    ///   tiles apply to them in turn.
+    /// * `<` / `>` - one-sided splitter, only the left/upper or right/lower
+    ///   beam is emitted.
+    /// * `/` and `\` - mirrors, the beam turns through 90 degrees.
+    /// * `#` - absorber, the beam stops.
+    fn step(&self, beam: Beam) -> Result<Step, BeamError> {
+        let (row, col) = if beam.entering {
+            (beam.row, beam.col)
+        } else {
+            let Some(cell) = self.offset(beam.row, beam.col, beam.direction)? else {
+                return Ok(Step { landed: None, beams: Vec::new(), exits: 1 });
+            };
+            cell
+        };
 
-    while !beams.is_empty() {
-        let mut next_beams = HashSet::new();
+        let mut step = Step { landed: Some((row, col)), beams: Vec::new(), exits: 0 };
+        let direction = beam.direction;
+        let (side_a, side_b) = direction.sides();
+        match self.grid[row][col] {
+            '.' | 'S' => step.beams.push(Beam { row, col, direction, entering: false }),
+            '^' => self.emit_sides(&mut step, (row, col), direction, &[side_a, side_b])?,
+            '<' => self.emit_sides(&mut step, (row, col), direction, &[side_a])?,
+            '>' => self.emit_sides(&mut step, (row, col), direction, &[side_b])?,
+            '/' => step.beams.push(Beam {
+                row,
+                col,
+                direction: direction.reflect_forward(),
+                entering: false,
+            }),
+            '\\' => step.beams.push(Beam {
+                row,
+                col,
+                direction: direction.reflect_back(),
+                entering: false,
+            }),
+            '#' => {}
+            tile => return Err(BeamError::UnknownTile { row, col, tile }),
+        }
+        Ok(step)
+    }
 
-        for &(row, col) in &beams {
-            // Move beam down one row.
-            let new_row = row + 1;
+    fn is_splitter(&self, row: usize, col: usize) -> bool {
+        matches!(self.grid[row][col], '^' | '<' | '>')
+    }
+
+    /// Visits every reachable beam state once, so loops terminate.
+    /// Each distinct beam state that lands on a splitter counts as one split.
+    fn trace(&self, start: Beam) -> Result<Trace, BeamError> {
+        let mut trace = Trace {
+            splits: 0,
+            beam_cells: HashSet::new(),
+            splitters_hit: HashSet::new(),
+        };
+        trace.beam_cells.insert((start.row, start.col));
+
+        let mut seen: HashSet<Beam> = HashSet::new();
+        let mut frontier = vec![start];
+        seen.insert(start);
+
+        while let Some(beam) = frontier.pop() {
+            let step = self.step(beam)?;
+            if let Some((row, col)) = step.landed {
+                if self.is_splitter(row, col) {
+                    trace.splits += 1;
+                    trace.splitters_hit.insert((row, col));
+                } else {
+                    trace.beam_cells.insert((row, col));
+                }
+            }
 
-            // Check if beam exits manifold.
-            if new_row >= grid.len() {
-                continue;
+            for next in step.beams {
+                if seen.insert(next) {
+                    frontier.push(next);
+                }
             }
+        }
+
+        Ok(trace)
+    }
+
+    /// Counts the timelines that leave the manifold.
+    ///
+    /// Like the original row-by-row approach, beams are tracked with counts
+    /// so timelines sharing a state are advanced together. A beam state that
+    /// can reach itself would produce unbounded timelines, so that is
+    /// reported as an error before counting starts.
+    ///
+    /// Counts can double at every splitter row, so they are accumulated with
+    /// the given `counting` strategy, which reports overflow instead of wrapping.
+    fn count_timelines<C: Counting>(
+        &self,
+        start: Beam,
+        counting: &C,
+    ) -> Result<Timelines<C::Value>, BeamError> {
+        self.check_acyclic(start)?;
+
+        let mut timelines = Timelines { total: counting.zero(), per_cell: HashMap::new() };
+        let mut beams: HashMap<Beam, C::Value> = HashMap::new();
+        beams.insert(start, counting.one());
+
+        while !beams.is_empty() {
+            let mut next_beams: HashMap<Beam, C::Value> = HashMap::new();
+
+            for (&beam, count) in &beams {
+                // A beam entering a cell is counted there once it has met the tile.
+                if !beam.entering {
+                    let cell_total = timelines
+                        .per_cell
+                        .entry((beam.row, beam.col))
+                        .or_insert_with(|| counting.zero());
+                    *cell_total = counting.add(cell_total, count)?;
+                }
+
+                let step = self.step(beam)?;
 
-            let ch = grid[new_row][col];
-
-            assert_ne!(ch, 'S', "Don't expect to find S during beam traversal!");
-            if ch == '.' {
-                // Continue moving down.
-                next_beams.insert((new_row, col));
-            } else if ch == '^' {
-                // Split! Beam stops, two new beams created to left and right.
-                splits += 1;
-
-                let left_col = col as i32 - 1;
-                let right_col = col as i32 + 1;
-
-                assert!(
-                    in_bounds(&grid, new_row as i32, left_col),
-                    "Left split out of bounds at ({}, {})",
-                    new_row,
-                    left_col
-                );
-                assert!(
-                    in_bounds(&grid, new_row as i32, right_col),
-                    "Right split out of bounds at ({}, {})",
-                    new_row,
-                    right_col
-                );
-
-                next_beams.insert((new_row, left_col as usize));
-                next_beams.insert((new_row, right_col as usize));
+                // Paths leaving the manifold are complete - count all their timelines.
+                for _ in 0..step.exits {
+                    timelines.total = counting.add(&timelines.total, count)?;
+                }
+
+                for next_beam in step.beams {
+                    let total = next_beams.entry(next_beam).or_insert_with(|| counting.zero());
+                    *total = counting.add(total, count)?;
+                }
             }
+
+            beams = next_beams;
         }
 
-        beams = next_beams;
+        Ok(timelines)
     }
 
-    splits
+    /// Depth-first search over beam states looking for a state that leads
+    /// back to itself.
+    fn check_acyclic(&self, start: Beam) -> Result<(), BeamError> {
+        // Beams whose successors are all fully explored.
+        let mut finished: HashSet<Beam> = HashSet::new();
+        // Beams on the current search path.
+        let mut on_path: HashSet<Beam> = HashSet::new();
+        let mut stack: Vec<(Beam, Vec<Beam>)> = Vec::new();
+
+        let successors =
+            |beam: Beam| -> Result<Vec<Beam>, BeamError> { Ok(self.step(beam)?.beams) };
+
+        on_path.insert(start);
+        stack.push((start, successors(start)?));
+
+        while let Some((beam, pending)) = stack.last_mut() {
+            match pending.pop() {
+                Some(next) if on_path.contains(&next) => {
+                    return Err(BeamError::Loop { beam: next });
+                }
+                Some(next) if !finished.contains(&next) => {
+                    on_path.insert(next);
+                    let next_successors = successors(next)?;
+                    stack.push((next, next_successors));
+                }
+                Some(_) => {}
+                None => {
+                    let beam = *beam;
+                    on_path.remove(&beam);
+                    finished.insert(beam);
+                    stack.pop();
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
-fn part2(input: &str) -> i64 {
+fn part1(input: &str, config: BeamConfig) -> Trace {
     let grid = parse_grid(input);
     let (start_row, start_col) = find_start(&grid);
+    let manifold = Manifold { grid: &grid, config };
 
-    let mut timelines = 0;
-    // Track positions with counts - if multiple timelines reach the same position,
-    // they'll behave identically from that point forward.
-    let mut beams: HashMap<(usize, usize), i64> = HashMap::new();
-    beams.insert((start_row, start_col), 1);
+    manifold
+        .trace(Beam {
+            row: start_row,
+            col: start_col,
+            direction: Direction::Down,
+            entering: false,
+        })
+        .unwrap_or_else(|err| panic!("{}", err))
+}
 
-    while !beams.is_empty() {
-        let mut next_beams: HashMap<(usize, usize), i64> = HashMap::new();
+fn part2<C: Counting>(
+    input: &str,
+    config: BeamConfig,
+    counting: &C,
+) -> Result<Timelines<C::Value>, BeamError> {
+    let grid = parse_grid(input);
+    let (start_row, start_col) = find_start(&grid);
+    let manifold = Manifold { grid: &grid, config };
 
-        for (&(row, col), &count) in &beams {
-            // Move beam down one row.
-            let new_row = row + 1;
+    manifold
+        .count_timelines(
+            Beam {
+                row: start_row,
+                col: start_col,
+                direction: Direction::Down,
+                entering: false,
+            },
+            counting,
+        )
+}
 
-            // Check if beam exits manifold.
-            if new_row >= grid.len() {
-                // This path is complete - count all timelines at this position.
-                timelines += count;
-                continue;
-            }
+/// Draws the manifold with beam coverage marked.
+///
+/// Legend: `|` a beam passed through, `*` a splitter that was hit, `^`/`<`/`>`
+/// a splitter no beam reached. Other tiles are drawn as-is.
+fn render_coverage(grid: &[Vec<char>], trace: &Trace) -> String {
+    let mut output = String::new();
+    for (row, row_vec) in grid.iter().enumerate() {
+        for (col, &ch) in row_vec.iter().enumerate() {
+            output.push(if trace.splitters_hit.contains(&(row, col)) {
+                '*'
+            } else if ch == '.' && trace.beam_cells.contains(&(row, col)) {
+                '|'
+            } else {
+                ch
+            });
+        }
+        output.push('\n');
+    }
+    output
+}
 
-            let ch = grid[new_row][col];
-
-            assert_ne!(ch, 'S', "Don't expect to find S during beam traversal!");
-            if ch == '.' {
-                // Continue moving down.
-                *next_beams.entry((new_row, col)).or_insert(0) += count;
-            } else if ch == '^' {
-                // Split! Create two new timelines for each timeline at this position.
-                let left_col = col as i32 - 1;
-                let right_col = col as i32 + 1;
-
-                assert!(
-                    in_bounds(&grid, new_row as i32, left_col),
-                    "Left split out of bounds at ({}, {})",
-                    new_row,
-                    left_col
-                );
-                assert!(
-                    in_bounds(&grid, new_row as i32, right_col),
-                    "Right split out of bounds at ({}, {})",
-                    new_row,
-                    right_col
-                );
-
-                *next_beams.entry((new_row, left_col as usize)).or_insert(0) += count;
-                *next_beams.entry((new_row, right_col as usize)).or_insert(0) += count;
-            }
+/// Single-character summary of a timeline count: the count itself for 1-9,
+/// then `a` for two digits, `b` for three and so on, `+` past `z`.
+fn heat_char(count: &str) -> char {
+    match count.len() {
+        1 => count.chars().next().unwrap(),
+        digits @ 2..=27 => (b'a' + (digits - 2) as u8) as char,
+        _ => '+',
+    }
+}
+
+/// Draws the manifold with each beam cell replaced by its [`heat_char`].
+fn render_heatmap<V: fmt::Display>(grid: &[Vec<char>], timelines: &Timelines<V>) -> String {
+    let mut output = String::new();
+    for (row, row_vec) in grid.iter().enumerate() {
+        for (col, &ch) in row_vec.iter().enumerate() {
+            output.push(match timelines.per_cell.get(&(row, col)) {
+                Some(count) => heat_char(&count.to_string()),
+                None => ch,
+            });
         }
+        output.push('\n');
+    }
+    output
+}
+
+/// Writes a plain-text PPM image with one pixel per cell: beam cells shaded
+/// from blue (fewest timelines) to yellow (most) on a log scale, hit
+/// splitters red, unreached splitters grey, and everything else black.
+fn write_ppm<V: fmt::Display>(
+    path: &str,
+    grid: &[Vec<char>],
+    trace: &Trace,
+    timelines: &Timelines<V>,
+) {
+    // Going through the decimal string works for every counter type.
+    let log_counts: HashMap<(usize, usize), f64> = timelines
+        .per_cell
+        .iter()
+        .map(|(&cell, count)| (cell, count.to_string().parse::<f64>().unwrap().ln_1p()))
+        .collect();
+    let max_log = log_counts.values().copied().fold(f64::MIN_POSITIVE, f64::max);
+
+    let mut image = format!("P3\n{} {}\n255\n", grid[0].len(), grid.len());
+    for (row, row_vec) in grid.iter().enumerate() {
+        for (col, &ch) in row_vec.iter().enumerate() {
+            let (r, g, b) = if trace.splitters_hit.contains(&(row, col)) {
+                (255, 0, 0)
+            } else if matches!(ch, '^' | '<' | '>') {
+                (128, 128, 128)
+            } else if trace.beam_cells.contains(&(row, col)) {
+                let log_count = log_counts.get(&(row, col)).copied().unwrap_or(0.0);
+                let heat = (log_count / max_log * 255.0) as u8;
+                (heat, heat, 255 - heat)
+            } else {
+                (0, 0, 0)
+            };
+            image.push_str(&format!("{} {} {}\n", r, g, b));
+        }
+    }
+
+    fs::write(path, image).unwrap_or_else(|err| panic!("Failed to write {}: {}", path, err));
+}
+
+/// Which visualisations to produce alongside the answers.
+struct RenderOptions {
+    coverage: bool,
+    heatmap: bool,
+    ppm_path: Option<String>,
+}
+
+/// Both parts and any visualisations, for whichever counting strategy was chosen.
+struct Run<'a> {
+    input: &'a str,
+    config: BeamConfig,
+    render: RenderOptions,
+}
 
-        beams = next_beams;
+impl WithCounting for Run<'_> {
+    type Output = ();
+
+    fn run<C: Counting>(self, counting: &C) {
+        run(self.input, self.config, counting, &self.render);
     }
+}
+
+fn run<C: Counting>(input: &str, config: BeamConfig, counting: &C, render: &RenderOptions) {
+    let grid = parse_grid(input);
 
-    timelines
+    let trace = part1(input, config);
+    if render.coverage {
+        print!("{}", render_coverage(&grid, &trace));
+    }
+    println!("{}", trace.splits);
+
+    // A looping beam is an answer in itself: there's no bound on the timelines.
+    let timelines = match part2(input, config, counting) {
+        Ok(timelines) => timelines,
+        Err(err @ BeamError::Loop { .. }) => {
+            println!("{}", err);
+            return;
+        }
+        Err(err) => panic!("{}", err),
+    };
+    if render.heatmap {
+        print!("{}", render_heatmap(&grid, &timelines));
+    }
+    if let Some(path) = &render.ppm_path {
+        write_ppm(path, &grid, &trace, &timelines);
+    }
+    println!("{}", timelines.total);
 }
 
 fn main() {
     let input = include_str!("../../../../inputs/day07.txt");
 
-    println!("{}", part1(input));
-    println!("{}", part2(input));
+    // `--edges top,bottom,left,right` overrides the puzzle's edge rules.
+    let args: Vec<String> = std::env::args().collect();
+    let option = |name: &str| option_value(&args, name);
+    let config = option("--edges")
+        .map(|spec| BeamConfig::parse(&spec))
+        .unwrap_or_else(BeamConfig::puzzle);
+
+    // `--count u128|big|mod:<p>` picks how part 2 timelines are counted.
+    let count_mode: CountMode = option("--count")
+        .map(|mode| mode.parse().unwrap_or_else(|err| panic!("{}", err)))
+        .unwrap_or_default();
+
+    // `--render` and `--heatmap` print the manifold, `--ppm <path>` writes an image.
+    let render = RenderOptions {
+        coverage: args.iter().any(|arg| arg == "--render"),
+        heatmap: args.iter().any(|arg| arg == "--heatmap"),
+        ppm_path: option("--ppm"),
+    };
+
+    count_mode.with(Run { input, config, render });
 }
@@ -1,180 +1,327 @@
-use std::collections::BinaryHeap;
-use std::cmp::Reverse;
+use advent_of_code_2025::DisjointSet;
+use advent_of_code_2025::spatial::{ClosestPairs, Metric};
+use advent_of_code_2025::option_value;
+use std::fmt;
+use std::fmt::Write;
+use std::fs;
 
-#[derive(Debug, Clone, Copy)]
+/// A junction box position. Every box in an input has the same number of
+/// axes, but that number comes from the input rather than being fixed at 3.
+#[derive(Debug, Clone)]
 struct Point {
-    x: i32,
-    y: i32,
-    z: i32,
+    coordinates: Vec<i64>,
 }
 
 impl Point {
-    fn distance_squared(&self, other: &Point) -> i64 {
-        let dx = (self.x - other.x) as i64;
-        let dy = (self.y - other.y) as i64;
-        let dz = (self.z - other.z) as i64;
-        dx * dx + dy * dy + dz * dz
+    fn x(&self) -> i64 {
+        self.coordinates[0]
     }
 }
 
-struct UnionFind {
-    parent: Vec<usize>,
-    size: Vec<usize>,
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coordinates: Vec<String> = self.coordinates.iter().map(|c| c.to_string()).collect();
+        write!(f, "{}", coordinates.join(","))
+    }
 }
 
-impl UnionFind {
-    fn new(n: usize) -> Self {
-        UnionFind {
-            parent: (0..n).collect(),
-            size: vec![1; n],
+fn parse_points(input: &str) -> Vec<Point> {
+    let points: Vec<Point> = input
+        .lines()
+        .map(|line| Point {
+            coordinates: line.split(',').map(|s| s.trim().parse().unwrap()).collect(),
+        })
+        .collect();
+
+    if let Some(first) = points.first() {
+        let dimensions = first.coordinates.len();
+        for (line, point) in points.iter().enumerate() {
+            assert_eq!(
+                point.coordinates.len(),
+                dimensions,
+                "Line {} has {} coordinates, expected {} like the first line",
+                line + 1,
+                point.coordinates.len(),
+                dimensions
+            );
         }
     }
 
-    fn find(&mut self, x: usize) -> usize {
-        if self.parent[x] != x {
-            self.parent[x] = self.find(self.parent[x]);
-        }
-        self.parent[x]
+    points
+}
+
+fn parse_metric(name: &str) -> Metric {
+    match name {
+        "euclidean" => Metric::Euclidean,
+        "manhattan" => Metric::Manhattan,
+        "chebyshev" => Metric::Chebyshev,
+        _ => panic!("Unknown metric: {}", name),
     }
+}
 
-    fn union(&mut self, x: usize, y: usize) -> bool {
-        let root_x = self.find(x);
-        let root_y = self.find(y);
+/// Pairs of junction boxes, closest first, generated lazily from a k-d tree
+/// rather than materialising every pair up front.
+fn closest_pairs(points: &[Point], metric: Metric) -> ClosestPairs {
+    let coordinates: Vec<Vec<i64>> = points.iter().map(|p| p.coordinates.clone()).collect();
+    ClosestPairs::new(&coordinates, metric)
+}
 
-        if root_x == root_y {
-            return false; // Already in same circuit.
-        }
+/// Circuit sizes, largest first, after trying the closest `num_connections`
+/// pairs (including pairs that were already in the same circuit).
+fn clusters_after_connections(
+    points: &[Point],
+    metric: Metric,
+    num_connections: usize,
+) -> Vec<usize> {
+    let mut circuits = DisjointSet::new(points.len());
+    for (_, i, j) in closest_pairs(points, metric).take(num_connections) {
+        circuits.union(i, j); // Try to connect, even if already in same circuit.
+    }
+
+    let mut sizes = circuits.component_sizes();
+    sizes.sort_by(|a, b| b.cmp(a)); // Sort descending.
+    sizes
+}
+
+fn part1(input: &str, num_connections: usize, metric: Metric) -> i64 {
+    // Parse junction boxes.
+    let points = parse_points(input);
+
+    // Try to connect the closest num_connections pairs, then find the three largest circuits.
+    let sizes = clusters_after_connections(&points, metric, num_connections);
+
+    assert!(
+        sizes.len() >= 3,
+        "Expected at least 3 circuits, but found {} (try a smaller --connections)",
+        sizes.len()
+    );
 
-        // Union by size.
-        if self.size[root_x] < self.size[root_y] {
-            self.parent[root_x] = root_y;
-            self.size[root_y] += self.size[root_x];
-        } else {
-            self.parent[root_y] = root_x;
-            self.size[root_x] += self.size[root_y];
+    eprintln!("Circuit sizes: {:?}", sizes);
+    eprintln!("Top 3: {} × {} × {} = {}", sizes[0], sizes[1], sizes[2], sizes[0] * sizes[1] * sizes[2]);
+
+    (sizes[0] * sizes[1] * sizes[2]) as i64
+}
+
+/// One connection in the minimum spanning tree, in the order Kruskal's
+/// algorithm made it.
+#[derive(Debug, Clone, Copy)]
+struct MstEdge {
+    // 1-based position in the join sequence.
+    order: usize,
+    // 1-based position in the closest-pairs sequence, counting pairs that
+    // were already in the same circuit.
+    attempt: usize,
+    i: usize,
+    j: usize,
+    // Under the chosen metric; squared for Euclidean.
+    distance: i64,
+    // Number of circuits remaining once this connection is made.
+    circuits_after: usize,
+}
+
+/// The minimum spanning tree (forest, if the boxes never all connect) over
+/// the junction boxes, built by Kruskal's algorithm.
+struct SpanningTree {
+    edges: Vec<MstEdge>,
+}
+
+impl SpanningTree {
+    /// Sum of the distances of every connection in the tree.
+    fn total_weight(&self) -> i64 {
+        self.edges.iter().map(|edge| edge.distance).sum()
+    }
+
+    /// Each change in the circuit count as `(attempt, circuits)`, starting
+    /// from one circuit per box before any pair is tried.
+    fn circuit_count_changes(&self, num_boxes: usize) -> Vec<(usize, usize)> {
+        std::iter::once((0, num_boxes))
+            .chain(self.edges.iter().map(|edge| (edge.attempt, edge.circuits_after)))
+            .collect()
+    }
+
+    fn to_csv(&self, points: &[Point]) -> String {
+        let mut csv =
+            String::from("order,attempt,from,to,from_point,to_point,distance,circuits_after\n");
+        for edge in &self.edges {
+            writeln!(
+                csv,
+                "{},{},{},{},\"{}\",\"{}\",{},{}",
+                edge.order,
+                edge.attempt,
+                edge.i,
+                edge.j,
+                points[edge.i],
+                points[edge.j],
+                edge.distance,
+                edge.circuits_after
+            )
+            .unwrap();
         }
-        true
+        csv
     }
 
-    fn get_circuit_sizes(&mut self) -> Vec<usize> {
-        let mut sizes = Vec::new();
-        for i in 0..self.parent.len() {
-            if self.find(i) == i {
-                sizes.push(self.size[i]);
-            }
+    fn to_dot(&self, points: &[Point]) -> String {
+        let mut dot = String::from("graph mst {\n");
+        for (i, point) in points.iter().enumerate() {
+            writeln!(dot, "    n{} [label=\"{}\"];", i, point).unwrap();
+        }
+        for edge in &self.edges {
+            writeln!(
+                dot,
+                "    n{} -- n{} [label=\"#{} d={}\"];",
+                edge.i, edge.j, edge.order, edge.distance
+            )
+            .unwrap();
         }
-        sizes
+        dot.push_str("}\n");
+        dot
     }
 }
 
-fn part1(input: &str, num_connections: usize) -> i64 {
-    // Parse junction boxes.
-    let points: Vec<Point> = input
-        .lines()
-        .map(|line| {
-            let parts: Vec<i32> = line.split(',').map(|s| s.parse().unwrap()).collect();
-            Point {
-                x: parts[0],
-                y: parts[1],
-                z: parts[2],
-            }
-        })
-        .collect();
+/// Connects closest pairs until every box is in one circuit (or the pairs
+/// run out), keeping only connections that join two circuits.
+fn kruskal(points: &[Point], metric: Metric) -> SpanningTree {
+    let mut circuits = DisjointSet::new(points.len());
+    let mut edges = Vec::new();
 
-    // Build all edges with distances.
-    let mut edges = BinaryHeap::new();
-    for i in 0..points.len() {
-        for j in i + 1..points.len() {
-            let dist = points[i].distance_squared(&points[j]);
-            edges.push(Reverse((dist, i, j)));
+    for (attempt, (distance, i, j)) in (1..).zip(closest_pairs(points, metric)) {
+        if circuits.component_count() <= 1 {
+            break;
+        }
+        if circuits.union(i, j) {
+            edges.push(MstEdge {
+                order: edges.len() + 1,
+                attempt,
+                i,
+                j,
+                distance,
+                circuits_after: circuits.component_count(),
+            });
         }
     }
 
-    // Try to connect the closest num_connections pairs.
-    let mut uf = UnionFind::new(points.len());
-    let mut attempts = 0;
+    SpanningTree { edges }
+}
 
-    while attempts < num_connections && !edges.is_empty() {
-        let Reverse((_, i, j)) = edges.pop().unwrap();
-        uf.union(i, j); // Try to connect, even if already in same circuit.
-        attempts += 1;
+/// Circuit sizes, largest first, when every pair at most `max_length`
+/// apart is connected (a single-linkage threshold cut). The length is a true
+/// distance, not squared, even for the Euclidean metric.
+///
+/// Only spanning tree connections can merge circuits, so counting those
+/// within the threshold is enough.
+fn clusters_within_distance(points: &[Point], metric: Metric, max_length: f64) -> Vec<usize> {
+    let max_distance = metric.threshold(max_length);
+    let mut circuits = DisjointSet::new(points.len());
+    for edge in kruskal(points, metric).edges {
+        if edge.distance > max_distance {
+            break;
+        }
+        circuits.union(edge.i, edge.j);
     }
 
-    // Get circuit sizes and find the three largest.
-    let mut sizes = uf.get_circuit_sizes();
+    let mut sizes = circuits.component_sizes();
     sizes.sort_by(|a, b| b.cmp(a)); // Sort descending.
+    sizes
+}
 
-    eprintln!("Circuit sizes: {:?}", sizes);
-    eprintln!("Top 3: {} × {} × {} = {}", sizes[0], sizes[1], sizes[2], sizes[0] * sizes[1] * sizes[2]);
+/// The smallest maximum distance whose threshold cut leaves exactly
+/// `num_clusters` circuits, or `None` if no distance does (out of range, or
+/// tied distances merge several circuits at once).
+///
+/// The distance is a true length, not squared, even for the Euclidean metric.
+fn distance_for_clusters(points: &[Point], metric: Metric, num_clusters: usize) -> Option<f64> {
+    if num_clusters == 0 || num_clusters > points.len() {
+        return None;
+    }
+    let mst = kruskal(points, metric);
+    let merges_needed = points.len() - num_clusters;
 
-    assert!(
-        sizes.len() >= 3,
-        "Expected at least 3 circuits, but found {}",
-        sizes.len()
-    );
+    let distance = match merges_needed {
+        0 => 0,
+        _ => mst.edges.get(merges_needed - 1)?.distance,
+    };
 
-    (sizes[0] * sizes[1] * sizes[2]) as i64
+    // With ties the cut at this distance may merge more than we want.
+    let merges = mst.edges.iter().take_while(|edge| edge.distance <= distance).count();
+    (merges == merges_needed).then_some(metric.length(distance))
 }
 
-fn part2(input: &str) -> i64 {
+fn part2(input: &str, metric: Metric) -> i64 {
     // Parse junction boxes.
-    let points: Vec<Point> = input
-        .lines()
-        .map(|line| {
-            let parts: Vec<i32> = line.split(',').map(|s| s.parse().unwrap()).collect();
-            Point {
-                x: parts[0],
-                y: parts[1],
-                z: parts[2],
-            }
-        })
-        .collect();
-
-    // Build all edges with distances.
-    let mut edges = BinaryHeap::new();
-    for i in 0..points.len() {
-        for j in i + 1..points.len() {
-            let dist = points[i].distance_squared(&points[j]);
-            edges.push(Reverse((dist, i, j)));
-        }
-    }
+    let points = parse_points(input);
 
     // Connect until all boxes are in one circuit.
-    let mut uf = UnionFind::new(points.len());
-    let mut found_last_connection = false;
-    let mut last_connection = (0, 0);
-
-    while !edges.is_empty() {
-        let Reverse((_, i, j)) = edges.pop().unwrap();
-        if uf.union(i, j) {
-            // This was a successful connection (not already connected).
-            last_connection = (i, j);
-
-            // Check if we're down to 1 circuit.
-            let num_circuits = uf.get_circuit_sizes().len();
-            if num_circuits == 1 {
-                found_last_connection = true;
-                break;
-            }
-        }
-    }
+    let mst = kruskal(&points, metric);
+    let last_connection = mst.edges.last().filter(|edge| edge.circuits_after == 1);
+    let last_connection =
+        last_connection.expect("Expected to find last connection joining every circuit!");
+
+    let (i, j) = (last_connection.i, last_connection.j);
+    points[i].x() * points[j].x()
+}
+
+/// Writes the full spanning tree to `--mst-csv <path>` and/or `--mst-dot <path>`.
+fn export_mst(input: &str, metric: Metric, csv_path: Option<&str>, dot_path: Option<&str>) {
+    let points = parse_points(input);
+    let mst = kruskal(&points, metric);
 
-    assert!(found_last_connection, "Expected to find last connection before exiting loop!");
+    eprintln!("MST: {} connections, total weight {}", mst.edges.len(), mst.total_weight());
+    eprintln!("Circuit count changes: {:?}", mst.circuit_count_changes(points.len()));
 
-    let (i, j) = last_connection;
-    (points[i].x as i64) * (points[j].x as i64)
+    if let Some(path) = csv_path {
+        fs::write(path, mst.to_csv(&points))
+            .unwrap_or_else(|err| panic!("Failed to write {}: {}", path, err));
+    }
+    if let Some(path) = dot_path {
+        fs::write(path, mst.to_dot(&points))
+            .unwrap_or_else(|err| panic!("Failed to write {}: {}", path, err));
+    }
 }
 
 fn main() {
     let input = include_str!("../../../../inputs/day08.txt");
 
-    // Count number of junction boxes.
-    let num_boxes = input.lines().count();
+    let args: Vec<String> = std::env::args().collect();
+    let option = |name: &str| option_value(&args, name);
+
+    // `--connections N` sets how many closest pairs part 1 tries. The real
+    // input uses 1000; the example uses 10.
+    let num_connections = option("--connections")
+        .map_or(1000, |n| n.parse().expect("--connections should be a number"));
+
+    // `--metric euclidean|manhattan|chebyshev` picks how boxes are compared.
+    let metric = option("--metric").map_or(Metric::Euclidean, |name| parse_metric(&name));
+
+    println!("{}", part1(input, num_connections, metric));
+    println!("{}", part2(input, metric));
 
-    // For the example (20 boxes), use 10 connections.
-    // For the real input, use 1000 connections.
-    let num_connections = if num_boxes <= 20 { 10 } else { 1000 };
+    // `--max-distance d` reports the circuits formed by connecting every pair
+    // within `d`; `--clusters k` reports the distance that leaves `k` circuits.
+    // Both are true distances, not squared.
+    if let Some(max_distance) = option("--max-distance") {
+        let max_distance = max_distance.parse().expect("--max-distance should be a number");
+        let points = parse_points(input);
+        let sizes = clusters_within_distance(&points, metric, max_distance);
+        println!("Within {}: {} circuits, sizes {:?}", max_distance, sizes.len(), sizes);
+    }
+    if let Some(num_clusters) = option("--clusters") {
+        let num_clusters = num_clusters.parse().expect("--clusters should be a number");
+        let points = parse_points(input);
+        match distance_for_clusters(&points, metric, num_clusters) {
+            Some(distance) => {
+                let sizes = clusters_within_distance(&points, metric, distance);
+                println!(
+                    "{} circuits at distance {:.3}, sizes {:?}",
+                    num_clusters, distance, sizes
+                );
+            }
+            None => println!("No distance gives exactly {} circuits", num_clusters),
+        }
+    }
 
-    println!("{}", part1(input, num_connections));
-    println!("{}", part2(input));
+    let csv_path = option("--mst-csv");
+    let dot_path = option("--mst-dot");
+    if csv_path.is_some() || dot_path.is_some() {
+        export_mst(input, metric, csv_path.as_deref(), dot_path.as_deref());
+    }
 }
@@ -1,4 +1,7 @@
-use rayon::prelude::*;
+use advent_of_code_2025::rectilinear::{self, PolygonSummary, Rectangle};
+use advent_of_code_2025::RectilinearPolygon;
+use advent_of_code_2025::option_value;
+use std::fs;
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 struct Coordinate {
@@ -6,8 +9,8 @@ struct Coordinate {
     y: usize,
 }
 
-fn part1(input: &str) -> usize {
-    let red_tiles: Vec<Coordinate> = input
+fn parse_red_tiles(input: &str) -> Vec<Coordinate> {
+    input
         .lines()
         .map(|line| {
             let digits: Vec<usize> = line
@@ -16,204 +19,137 @@ fn part1(input: &str) -> usize {
                 .collect();
             assert_eq!(digits.len(), 2);
             Coordinate { x: digits[0], y: digits[1] }
-        }).collect();
-
-        let mut max_area = 0;
-        for (i, first_tile) in red_tiles.iter().enumerate() {
-            for second_tile in &red_tiles[i + 1..] {
-
-                let width = second_tile.x.abs_diff(first_tile.x) + 1;
-                let height = second_tile.y.abs_diff(first_tile.y) + 1;
-                let area = height * width;
-                if area > max_area {
-                    max_area = area;
-                }
-            }
-        }
-
-    max_area
-}
-
-fn point_in_polygon_ray_cast(
-    point: &Coordinate,
-    edges: &[(Coordinate, Coordinate)],
-) -> bool {
-    let mut count = 0;
-
-    for edge in edges {
-        // Only check vertical edges (where x coordinates are the same).
-        // We can get away with this because the problem description uses
-        // straight lines (either vertical or horizontal).
-
-        assert!(
-            edge.0.x == edge.1.x || edge.0.y == edge.1.y,
-            "Expected all edges to be straight lines, one that wasn't: {:?}", edge
-        );
-        if edge.0.x == edge.1.x {
-            let edge_x = edge.0.x;
-            let min_y = edge.0.y.min(edge.1.y);
-            let max_y = edge.0.y.max(edge.1.y);
-
-            // Does this vertical edge span across our point's y-coordinate?
-            // And is it to the right of our point?
-            if min_y <= point.y && point.y < max_y && edge_x > point.x {
-                count += 1;
-            }
-        }
-    }
-
-    count % 2 == 1
-}
-
-fn point_in_polygon_on_edge(
-    point: &Coordinate,
-    edges: &[(Coordinate, Coordinate)],
-) -> bool {
-    for edge in edges {
-        assert!(
-            edge.0.x == edge.1.x || edge.0.y == edge.1.y,
-            "Expected all edges to be straight lines, one that wasn't: {:?}", edge
-        );
-
-        if edge.0.x == edge.1.x {
-            // Vertical edge case.
-            let min_y = edge.0.y.min(edge.1.y);
-            let max_y = edge.0.y.max(edge.1.y);
-            if point.x == edge.0.x && point.y >= min_y && point.y <= max_y {
-                return true;
-            }
-        } else {
-            // Horizontal edge case.
-            let min_x = edge.0.x.min(edge.1.x);
-            let max_x = edge.0.x.max(edge.1.x);
-            if point.y == edge.0.y && point.x >= min_x && point.x <= max_x {
-                return true;
-            }
-        }
-    }
-    false
-}
-fn point_in_polygon(
-    point: &Coordinate,
-    edges: &[(Coordinate, Coordinate)],
-) -> bool {
-    point_in_polygon_on_edge(point, edges) || point_in_polygon_ray_cast(point, edges)
+        }).collect()
 }
 
-fn polygon_edges(coordinates: &[Coordinate]) -> Vec<(Coordinate, Coordinate)> {
-    // Big ole iterator fun to get
-    // - all edges
-    // - enure the last coord wraps to the fist for the final edge
-    coordinates
+fn as_vertices(red_tiles: &[Coordinate]) -> Vec<(i64, i64)> {
+    red_tiles
         .iter()
-        // Chain into the first item again to get last egde.
-        .chain(coordinates.iter().take(1))
-        .copied()
-        // Collect as windows needs a Vec.
-        .collect::<Vec<Coordinate>>()
-        .windows(2)
-        .map(|pair| (pair[0], pair[1]))
+        .map(|tile| (tile.x as i64, tile.y as i64))
         .collect()
 }
 
-fn is_rectangle_valid(
-    top_left: &Coordinate,
-    bottom_right: &Coordinate,
-    edges: &[(Coordinate, Coordinate)],
-) -> bool {
-    // Quick reject: check midpoints of each edge first.
-    let mid_x = (top_left.x + bottom_right.x) / 2;
-    let mid_y = (top_left.y + bottom_right.y) / 2;
-
-    if !point_in_polygon(&Coordinate { x: mid_x, y: top_left.y }, &edges) ||
-        !point_in_polygon(&Coordinate { x: mid_x, y: bottom_right.y }, &edges) ||
-        !point_in_polygon(&Coordinate { x: top_left.x, y: mid_y }, &edges) ||
-        !point_in_polygon(&Coordinate { x: bottom_right.x, y: mid_y }, &edges) {
-        return false;
-    }
-
-    // Top edge.
-    for x in top_left.x..=bottom_right.x {
-        if !point_in_polygon(&Coordinate { x, y: top_left.y }, &edges) {
-            return false;
-        }
-    }
-
-    // Right edge.
-    for y in top_left.y..=bottom_right.y {
-        if !point_in_polygon(&Coordinate { x: bottom_right.x, y }, &edges) {
-            return false;
+/// The largest rectangle with red tiles at opposite corners, anywhere.
+fn largest_rectangle(red_tiles: &[Coordinate]) -> Rectangle {
+    let mut best = (0, red_tiles[0], red_tiles[0]);
+    for (i, first_tile) in red_tiles.iter().enumerate() {
+        for second_tile in &red_tiles[i + 1..] {
+            let width = second_tile.x.abs_diff(first_tile.x) + 1;
+            let height = second_tile.y.abs_diff(first_tile.y) + 1;
+            let area = height * width;
+            if area > best.0 {
+                best = (area, *first_tile, *second_tile);
+            }
         }
     }
 
-    // Bottom edge.
-    for x in top_left.x..=bottom_right.x {
-        if !point_in_polygon(&Coordinate { x, y: bottom_right.y }, &edges) {
-            return false;
-        }
+    let (area, first_tile, second_tile) = best;
+    Rectangle {
+        area: area as i128,
+        corners: (
+            (first_tile.x as i64, first_tile.y as i64),
+            (second_tile.x as i64, second_tile.y as i64),
+        ),
     }
+}
 
-    // Left edge.
-    for y in top_left.y..=bottom_right.y {
-        if !point_in_polygon(&Coordinate { x: top_left.x, y }, &edges) {
-            return false;
-        }
-    }
+/// The largest rectangle with red tiles at opposite corners that only
+/// covers red or green tiles, along with a summary of the loop.
+fn largest_inside_rectangle(red_tiles: &[Coordinate]) -> (PolygonSummary, Rectangle) {
+    let vertices = as_vertices(red_tiles);
+    // Reject malformed loops before the expensive search.
+    let summary = rectilinear::validate(&vertices).unwrap_or_else(|err| panic!("{}", err));
+    let polygon = RectilinearPolygon::new(&vertices);
+    let best = polygon.largest_vertex_rectangle().expect("Should have gotten an area");
+    (summary, best)
+}
 
-    true
+fn part1(input: &str) -> usize {
+    largest_rectangle(&parse_red_tiles(input)).area as usize
 }
 
 fn part2(input: &str) -> usize {
-    let red_tiles: Vec<Coordinate> = input
-        .lines()
-        .map(|line| {
-            let digits: Vec<usize> = line
-                .split(',')
-                .map(|s| s.parse::<usize>().expect("Should be a number"))
-                .collect();
-            assert_eq!(digits.len(), 2);
-            Coordinate { x: digits[0], y: digits[1] }
-        }).collect();
+    let red_tiles = parse_red_tiles(input);
+    let (summary, best) = largest_inside_rectangle(&red_tiles);
+    eprintln!(
+        "Polygon: {} vertices, {:?}, area {}, {} tiles",
+        red_tiles.len(), summary.orientation, summary.area, summary.tiles
+    );
+    eprintln!("Largest rectangle: {:?} to {:?}", best.corners.0, best.corners.1);
+    best.area as usize
+}
 
-    // Generate all pairs with their areas.
-    let mut candidates: Vec<(usize, &Coordinate, &Coordinate)> = Vec::new();
-    for (i, first_tile) in red_tiles.iter().enumerate() {
-        for second_tile in &red_tiles[i + 1..] {
-            let width = second_tile.x.abs_diff(first_tile.x) + 1;
-            let height = second_tile.y.abs_diff(first_tile.y) + 1;
-            candidates.push((width * height, first_tile, second_tile));
-        }
+/// SVG `x y width height` attributes covering every tile of `rectangle`.
+fn tile_bounds(rectangle: &Rectangle) -> String {
+    let ((x1, y1), (x2, y2)) = rectangle.corners;
+    format!(
+        r#"x="{}" y="{}" width="{}" height="{}""#,
+        x1.min(x2) as f64 - 0.5,
+        y1.min(y2) as f64 - 0.5,
+        x1.abs_diff(x2) + 1,
+        y1.abs_diff(y2) + 1
+    )
+}
+
+/// Writes the red-tile loop with its green interior, every red tile as a
+/// candidate corner, and the part 1 (blue) and part 2 (orange) rectangles.
+fn write_svg(path: &str, red_tiles: &[Coordinate], part1: &Rectangle, part2: &Rectangle) {
+    let vertices = as_vertices(red_tiles);
+    let min_x = vertices.iter().map(|v| v.0).min().unwrap() as f64 - 1.0;
+    let min_y = vertices.iter().map(|v| v.1).min().unwrap() as f64 - 1.0;
+    let width = vertices.iter().map(|v| v.0).max().unwrap() as f64 + 1.0 - min_x;
+    let height = vertices.iter().map(|v| v.1).max().unwrap() as f64 + 1.0 - min_y;
+    // Keep markers visible whether the grid is ten tiles across or a million.
+    let marker = (width.max(height) / 300.0).max(0.3);
+
+    let points: Vec<String> = vertices.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+        min_x, min_y, width, height
+    );
+    svg.push('\n');
+    svg.push_str(&format!(
+        r##"<polygon points="{}" fill="#7fc97f" stroke="#d62728" stroke-width="{}"/>"##,
+        points.join(" "),
+        marker / 2.0
+    ));
+    svg.push('\n');
+    for (rectangle, colour) in [(part1, "#1f77b4"), (part2, "#ff7f0e")] {
+        svg.push_str(&format!(
+            r#"<rect {} fill="{}" fill-opacity="0.3" stroke="{}" stroke-width="{}"/>"#,
+            tile_bounds(rectangle),
+            colour,
+            colour,
+            marker / 2.0
+        ));
+        svg.push('\n');
     }
+    for (x, y) in &vertices {
+        svg.push_str(&format!(
+            r##"<circle cx="{}" cy="{}" r="{}" fill="#d62728"/>"##,
+            x, y, marker
+        ));
+        svg.push('\n');
+    }
+    svg.push_str("</svg>\n");
 
-    // Sort by area from largest to smallest.
-    candidates.sort_by(|a, b| b.0.cmp(&a.0));
-
-    let edges = polygon_edges(&red_tiles);
-    
-    candidates
-        .par_iter()
-        .find_map_first(|(area, first_tile, second_tile)| {
-            let top_left = Coordinate {
-                x: first_tile.x.min(second_tile.x),
-                y: first_tile.y.min(second_tile.y)
-            };
-            let bottom_right = Coordinate {
-                x: first_tile.x.max(second_tile.x),
-                y: first_tile.y.max(second_tile.y)
-            };
-
-            if is_rectangle_valid(&top_left, &bottom_right, &edges) {
-                Some(*area)
-            } else {
-                None
-            }
-        })
-        .expect("Should have gotten an area")
+    fs::write(path, svg).unwrap_or_else(|err| panic!("Failed to write {}: {}", path, err));
 }
 
 fn main() {
     let input = include_str!("../../../../inputs/day09.txt");
 
+    // `--svg <path>` draws the polygon and both answers.
+    let args: Vec<String> = std::env::args().collect();
+    let option = |name: &str| option_value(&args, name);
+
     println!("{}", part1(input));
     println!("{}", part2(input)); // 226926789 too low
-}
\ No newline at end of file
+
+    if let Some(path) = option("--svg") {
+        let red_tiles = parse_red_tiles(input);
+        let part1 = largest_rectangle(&red_tiles);
+        let (_, part2) = largest_inside_rectangle(&red_tiles);
+        write_svg(&path, &red_tiles, &part1, &part2);
+    }
+}
@@ -1,6 +1,12 @@
-use std::collections::HashSet;
-use rayon::prelude::*;
-
+use advent_of_code_2025::elimination::{checked_lcm, ArithmeticOverflow, Rref};
+use advent_of_code_2025::gf2::{self, BitVector};
+use advent_of_code_2025::jobs::{Deadline, Expired, JobReport, JobRunner, Outcome};
+use advent_of_code_2025::option_value;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Eq, Hash, PartialEq)]
 struct Machine {
     // On/off indicators.
     indicator_lights: Vec<bool>,
@@ -8,12 +14,143 @@ struct Machine {
     joltage_requirements: Vec<usize>,
 }
 
-#[derive(Hash, Eq, PartialEq)]
-struct IndicatorLightsAndCost {
-    indicator_lights: Vec<bool>,
-    cost: usize,
+/// Why a machine can't be configured.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MachineError {
+    /// A button is wired to a light or counter the machine doesn't have.
+    WiringOutOfRange { button: usize, index: usize, available: usize },
+    /// No combination of buttons toggles the lights into the target pattern.
+    UnreachableLights,
+    /// No non-negative whole number of presses reaches the joltage requirements.
+    NoJoltageSolution,
+    Overflow(ArithmeticOverflow),
+    /// The solver ran past the per-machine timeout.
+    TimedOut,
+}
+
+impl fmt::Display for MachineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MachineError::WiringOutOfRange { button, index, available } => write!(
+                f,
+                "Button {} is wired to {} but there are only {}",
+                button, index, available
+            ),
+            MachineError::UnreachableLights => {
+                write!(f, "No combination of buttons turns on the lights")
+            }
+            MachineError::NoJoltageSolution => {
+                write!(f, "No whole number of presses meets the joltage requirements")
+            }
+            MachineError::Overflow(err) => write!(f, "{}", err),
+            MachineError::TimedOut => write!(f, "Gave up at the timeout"),
+        }
+    }
+}
+
+impl From<ArithmeticOverflow> for MachineError {
+    fn from(err: ArithmeticOverflow) -> Self {
+        MachineError::Overflow(err)
+    }
+}
+
+impl From<Expired> for MachineError {
+    fn from(_: Expired) -> Self {
+        MachineError::TimedOut
+    }
+}
+
+/// A machine that couldn't be configured, by its 1-based line in the input.
+struct Failure {
+    line: usize,
+    error: MachineError,
+}
+
+/// Total presses over the machines that could be configured, plus those that couldn't.
+struct PartResult {
+    presses: usize,
+    failures: Vec<Failure>,
 }
 
+/// Light states up to this many lights are tracked in a flat array rather than a map.
+const DENSE_LIGHTS: usize = 20;
+
+/// Presses needed to reach each light state seen so far by one side of the BFS.
+enum Distances {
+    /// Indexed by state, `u8::MAX` for unseen.
+    Dense(Vec<u8>),
+    Sparse(HashMap<u128, u8>),
+}
+
+impl Distances {
+    fn new(lights: usize) -> Self {
+        if lights <= DENSE_LIGHTS {
+            Distances::Dense(vec![u8::MAX; 1 << lights])
+        } else {
+            Distances::Sparse(HashMap::new())
+        }
+    }
+
+    fn get(&self, state: u128) -> Option<u8> {
+        match self {
+            Distances::Dense(distances) => Some(distances[state as usize]).filter(|&d| d != u8::MAX),
+            Distances::Sparse(distances) => distances.get(&state).copied(),
+        }
+    }
+
+    fn insert(&mut self, state: u128, distance: u8) {
+        match self {
+            Distances::Dense(distances) => distances[state as usize] = distance,
+            Distances::Sparse(distances) => {
+                distances.insert(state, distance);
+            }
+        }
+    }
+}
+
+/// One direction of a bidirectional BFS: everything seen from its root, and the states
+/// at the current depth.
+struct BfsSide {
+    distances: Distances,
+    frontier: Vec<u128>,
+}
+
+impl BfsSide {
+    fn new(lights: usize, root: u128) -> Self {
+        let mut distances = Distances::new(lights);
+        distances.insert(root, 0);
+        BfsSide { distances, frontier: vec![root] }
+    }
+
+    /// Expands one full level, returning the shortest total distance through any state
+    /// that `other` has already seen.
+    fn expand(&mut self, masks: &[u128], other: &BfsSide) -> Option<usize> {
+        let mut met: Option<usize> = None;
+        let mut next = Vec::new();
+        for &state in &self.frontier {
+            let distance = self.distances.get(state).unwrap() + 1;
+            for &mask in masks {
+                let neighbour = state ^ mask;
+                if self.distances.get(neighbour).is_some() {
+                    continue;
+                }
+                if let Some(remaining) = other.distances.get(neighbour) {
+                    let total = distance as usize + remaining as usize;
+                    met = Some(met.map_or(total, |best| best.min(total)));
+                }
+                self.distances.insert(neighbour, distance);
+                next.push(neighbour);
+            }
+        }
+        self.frontier = next;
+        met
+    }
+}
+
+/// Light states with the given lights on, one bit per light.
+fn light_mask(lights: impl Iterator<Item = usize>) -> u128 {
+    lights.fold(0, |mask, light| mask | 1 << light)
+}
 
 fn update_indicator_lights(
     indicator_lights: &[bool],
@@ -26,91 +163,349 @@ fn update_indicator_lights(
     new_indicator_lights
 }
 
-/// Transform the augmented matrix into row echelon form using Gaussian elimination.
-/// Returns the column indices of pivot columns (variables determined by the system).
-/// Non-pivot columns correspond to free variables that can be chosen arbitrarily.
-fn gaussian_eliminate(matrix: &mut Vec<Vec<i64>>) -> Vec<usize> {
-    let rows = matrix.len();
-    let cols = matrix[0].len();
-
-    let mut pivot_row = 0;
-    let mut pivot_cols = Vec::new();
-
-    // Process each column (except the last, which is the target/augmented column).
-    for col in 0..cols - 1 {
-        // Find a row with non-zero entry in this column to use as pivot.
-        let mut found = None;
-        for row in pivot_row..rows {
-            if matrix[row][col] != 0 {
-                found = Some(row);
-                break;
-            }
+/// Sum of terms that are each `None` if they overflowed, or `None` if the sum does.
+fn checked_sum(mut terms: impl Iterator<Item = Option<i128>>) -> Option<i128> {
+    terms.try_fold(0i128, |total, term| total.checked_add(term?))
+}
+
+/// Largest magnitude `constant + sum(coefficients[f] * free[f])` can reach with every
+/// `0 <= free[f] <= bounds[f]`, failing if that doesn't fit in an `i128`.
+///
+/// Every partial sum is no bigger, so once this succeeds the search can work in plain
+/// `i128` arithmetic without overflowing.
+fn affine_magnitude(
+    constant: i128,
+    coefficients: &[i128],
+    bounds: &[i64],
+) -> Result<i128, ArithmeticOverflow> {
+    coefficients.iter().zip(bounds).try_fold(constant.abs(), |total, (&coefficient, &bound)| {
+        coefficient
+            .checked_mul(bound as i128)
+            .and_then(|term| total.checked_add(term.abs()))
+            .ok_or(ArithmeticOverflow)
+    })
+}
+
+/// A pivot variable written in terms of the free variables:
+/// `(constant - sum(coefficients[f] * free[f])) / divisor`, with `divisor > 0`.
+///
+/// Widened from the `i64` rows so that products with the free variables' values fit.
+struct PivotExpression {
+    col: usize,
+    constant: i128,
+    coefficients: Vec<i128>,
+    divisor: i128,
+}
+
+impl PivotExpression {
+    /// `constant - sum(coefficients[f] * free[f])` for a full assignment.
+    fn numerator(&self, free_vals: &[i64]) -> i128 {
+        self.constant
+            - self
+                .coefficients
+                .iter()
+                .zip(free_vals)
+                .map(|(a, &x)| a * x as i128)
+                .sum::<i128>()
+    }
+}
+
+/// Branch-and-bound search for the fewest total presses over the free variables of the
+/// reduced joltage system.
+///
+/// Every pivot variable and the total are affine in the free variables, so for a partial
+/// assignment the remaining free variables' bounds give the best each can still reach.
+/// A branch is cut as soon as some pivot can no longer be non-negative, or the total can
+/// no longer beat the best solution found so far.
+struct JoltageSearch {
+    num_buttons: usize,
+    free_cols: Vec<usize>,
+    // Upper bound on each free variable.
+    bounds: Vec<i64>,
+    pivots: Vec<PivotExpression>,
+    // `scale * total = objective_constant + sum(objective[f] * free[f])`.
+    objective_constant: i128,
+    objective: Vec<i128>,
+    best: Option<(i128, Vec<i64>)>,
+    deadline: Deadline,
+    // Search nodes visited, so the clock is only read every so often.
+    nodes: usize,
+    gave_up: bool,
+}
+
+/// Search nodes between checks of the deadline.
+const DEADLINE_CHECK_INTERVAL: usize = 1024;
+
+impl JoltageSearch {
+    /// Returns `None` if the system is inconsistent, so there is no solution at all, or
+    /// `Err` if the pivots or total could overflow an `i128` somewhere in the search.
+    fn new(
+        rref: &Rref,
+        button_bounds: &[i64],
+        deadline: Deadline,
+    ) -> Result<Option<Self>, ArithmeticOverflow> {
+        if !rref.is_consistent() {
+            return Ok(None);
         }
 
-        // If no pivot found, this is a free variable column. Skip it.
-        let Some(swap_row) = found else { continue };
-
-        // Swap the pivot row into position.
-        matrix.swap(pivot_row, swap_row);
-        pivot_cols.push(col);
-
-        // Eliminate all entries below the pivot.
-        // We scale rows to avoid fractions (integer arithmetic only).
-        // Row[r] = Row[r] * pivot_val - Row[pivot] * row_val makes Row[r][col] = 0.
-        for row in pivot_row + 1..rows {
-            if matrix[row][col] != 0 {
-                let pivot_val = matrix[pivot_row][col];
-                let row_val = matrix[row][col];
-                for c in 0..cols {
-                    matrix[row][c] = matrix[row][c] * pivot_val - matrix[pivot_row][c] * row_val;
-                }
-            }
+        let num_buttons = rref.variables();
+        let free_cols = rref.free_cols();
+        let pivots: Vec<PivotExpression> = rref
+            .pivot_cols()
+            .iter()
+            .zip(rref.rows())
+            .map(|(&col, row)| PivotExpression {
+                col,
+                constant: row[num_buttons] as i128,
+                coefficients: free_cols.iter().map(|&f| row[f] as i128).collect(),
+                divisor: row[col] as i128,
+            })
+            .collect();
+
+        let scale = rref
+            .pivot_cols()
+            .iter()
+            .zip(rref.rows())
+            .try_fold(1, |acc, (&col, row)| checked_lcm(acc, row[col]))? as i128;
+        let pivot_constants =
+            pivots.iter().map(|pivot| (scale / pivot.divisor).checked_mul(pivot.constant));
+        let objective_constant = checked_sum(pivot_constants).ok_or(ArithmeticOverflow)?;
+        let objective = (0..free_cols.len())
+            .map(|f| {
+                checked_sum(pivots.iter().map(|pivot| {
+                    (scale / pivot.divisor).checked_mul(pivot.coefficients[f])
+                }))
+                .and_then(|pivot_total| scale.checked_sub(pivot_total))
+                .ok_or(ArithmeticOverflow)
+            })
+            .collect::<Result<Vec<i128>, _>>()?;
+
+        let bounds: Vec<i64> = free_cols.iter().map(|&f| button_bounds[f]).collect();
+        affine_magnitude(objective_constant, &objective, &bounds)?;
+        for pivot in &pivots {
+            affine_magnitude(pivot.constant, &pivot.coefficients, &bounds)?;
+        }
+
+        Ok(Some(JoltageSearch {
+            num_buttons,
+            bounds,
+            free_cols,
+            pivots,
+            objective_constant,
+            objective,
+            best: None,
+            deadline,
+            nodes: 0,
+            gave_up: false,
+        }))
+    }
+
+    /// The optimal press count for every button, if any non-negative integer solution exists,
+    /// or `Err(Expired)` if the deadline passed first.
+    fn solve(mut self) -> Result<Option<Vec<i64>>, Expired> {
+        let mut assigned = Vec::with_capacity(self.free_cols.len());
+        self.search(&mut assigned);
+        if self.gave_up {
+            return Err(Expired);
         }
 
-        pivot_row += 1;
+        let Some((_, free_vals)) = self.best else {
+            return Ok(None);
+        };
+        let mut solution = vec![0i64; self.num_buttons];
+        for (f, &col) in self.free_cols.iter().enumerate() {
+            solution[col] = free_vals[f];
+        }
+        for pivot in &self.pivots {
+            // A button can't be pressed more often than a counter it feeds needs, so this
+            // fits back in an `i64`.
+            let presses = pivot.numerator(&free_vals) / pivot.divisor;
+            solution[pivot.col] = i64::try_from(presses).expect("Should be within bounds");
+        }
+        Ok(Some(solution))
     }
 
-    pivot_cols
+    fn search(&mut self, assigned: &mut Vec<i64>) {
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(DEADLINE_CHECK_INTERVAL) && self.deadline.expired() {
+            self.gave_up = true;
+        }
+        if self.gave_up {
+            return;
+        }
+        let depth = assigned.len();
+
+        // Smallest total still reachable with the unassigned variables in their bounds.
+        let lower_bound = self.objective_constant
+            + (0..self.free_cols.len())
+                .map(|f| match assigned.get(f) {
+                    Some(&x) => self.objective[f] * x as i128,
+                    None => (self.objective[f] * self.bounds[f] as i128).min(0),
+                })
+                .sum::<i128>();
+        if self.best.as_ref().is_some_and(|(best, _)| lower_bound >= *best) {
+            return;
+        }
+
+        // Every pivot must still be able to reach zero or above.
+        let feasible = self.pivots.iter().all(|pivot| {
+            let largest = pivot.constant
+                - (0..self.free_cols.len())
+                    .map(|f| match assigned.get(f) {
+                        Some(&x) => pivot.coefficients[f] * x as i128,
+                        None => (pivot.coefficients[f] * self.bounds[f] as i128).min(0),
+                    })
+                    .sum::<i128>();
+            largest >= 0
+        });
+        if !feasible {
+            return;
+        }
+
+        if depth == self.free_cols.len() {
+            // Everything is fixed, so the bound is exact; it only remains to check the
+            // pivots come out as whole numbers.
+            let integral = self
+                .pivots
+                .iter()
+                .all(|pivot| pivot.numerator(assigned) % pivot.divisor == 0);
+            if integral {
+                self.best = Some((lower_bound, assigned.clone()));
+            }
+            return;
+        }
+
+        for value in 0..=self.bounds[depth] {
+            assigned.push(value);
+            self.search(assigned);
+            assigned.pop();
+        }
+    }
 }
 
 impl Machine {
-    fn turn_on_lights(&self) -> usize {
-        let start_position = IndicatorLightsAndCost {
-            indicator_lights: vec![false; self.indicator_lights.len()],
-            cost: 0,
-        };
-        
-        let mut seen_light_configurations: HashSet<Vec<bool>> = HashSet::new();
-
-        let mut frontier: Vec<IndicatorLightsAndCost> = vec![start_position];
-        while frontier.len() > 0 {
-            let mut new_frontier: Vec<IndicatorLightsAndCost> = Vec::new();
-            for indicator_lights_and_cost in frontier.iter() {
-                seen_light_configurations.insert(indicator_lights_and_cost.indicator_lights.clone());
-                let indicator_lights = &indicator_lights_and_cost.indicator_lights;
-                let cost = indicator_lights_and_cost.cost;
-                if indicator_lights == &self.indicator_lights {
-                    // We've found a solution, and since we're using BFS and cost increases by 1,
-                    // the first solution should be correct.
-                    return cost;
-                }
-                for button_wiring in self.button_wiring.iter() {
-                    let new_indicator_lights = update_indicator_lights(
-                        indicator_lights,
-                        button_wiring,
-                    );
-                    if !seen_light_configurations.contains(&new_indicator_lights) {
-                        new_frontier.push(IndicatorLightsAndCost {
-                            indicator_lights: new_indicator_lights,
-                            cost: cost + 1,
-                        });
-                    }
-                }
+    /// Bidirectional breadth-first search over light states packed into a `u128`, with
+    /// each button an XOR mask. Returns `None` if the target pattern can't be reached.
+    ///
+    /// Exponential in the number of lights, so only used to check
+    /// [`Machine::solve_lights`] on small machines.
+    fn turn_on_lights_bfs(&self) -> Option<usize> {
+        let lights = self.indicator_lights.len();
+        assert!(lights <= 128, "Should have at most 128 lights to search");
+        let masks: Vec<u128> = self
+            .button_wiring
+            .iter()
+            .map(|wiring| light_mask(wiring.iter().copied()))
+            .collect();
+        let target = light_mask((0..lights).filter(|&light| self.indicator_lights[light]));
+
+        if target == 0 {
+            return Some(0);
+        }
+        let mut forward = BfsSide::new(lights, 0);
+        let mut backward = BfsSide::new(lights, target);
+        // Grow whichever side has less to expand; finishing a whole level before stopping
+        // makes the first meeting the shortest.
+        while !forward.frontier.is_empty() && !backward.frontier.is_empty() {
+            let met = if forward.frontier.len() <= backward.frontier.len() {
+                forward.expand(&masks, &backward)
+            } else {
+                backward.expand(&masks, &forward)
+            };
+            if met.is_some() {
+                return met;
             }
-            frontier = new_frontier;
         }
+        None
+    }
 
-        unreachable!("Should have found a solution above");
+    /// How often to press each button to toggle the lights into the target pattern, using
+    /// as few presses as possible. Pressing a button twice undoes it, so each is pressed at
+    /// most once and this is a GF(2) system.
+    fn solve_lights(&self, deadline: &Deadline) -> Result<Vec<usize>, MachineError> {
+        let lights = self.indicator_lights.len();
+        self.check_wiring(lights)?;
+        let columns: Vec<BitVector> = self
+            .button_wiring
+            .iter()
+            .map(|wiring| BitVector::from_indices(lights, wiring.iter().copied()))
+            .collect();
+        let target = BitVector::from_indices(
+            lights,
+            (0..lights).filter(|&light| self.indicator_lights[light]),
+        );
+
+        let solution = gf2::min_weight_solution(&columns, &target, deadline)?;
+        if cfg!(debug_assertions) && lights <= DENSE_LIGHTS {
+            let total = solution.as_ref().map(BitVector::count_ones);
+            assert_eq!(total, self.turn_on_lights_bfs());
+        }
+
+        let solution = solution.ok_or(MachineError::UnreachableLights)?;
+        Ok((0..columns.len()).map(|button| solution.get(button) as usize).collect())
+    }
+
+    /// Checks every button is wired to one of the first `available` lights or counters.
+    fn check_wiring(&self, available: usize) -> Result<(), MachineError> {
+        for (button, wiring) in self.button_wiring.iter().enumerate() {
+            if let Some(&index) = wiring.iter().find(|&&index| index >= available) {
+                return Err(MachineError::WiringOutOfRange { button, index, available });
+            }
+        }
+        Ok(())
+    }
+
+    /// The light pattern after pressing each button `presses[button]` times.
+    fn lights_after(&self, presses: &[usize]) -> Vec<bool> {
+        let mut lights = vec![false; self.indicator_lights.len()];
+        for (wiring, &count) in self.button_wiring.iter().zip(presses) {
+            if count % 2 == 1 {
+                lights = update_indicator_lights(&lights, wiring);
+            }
+        }
+        lights
+    }
+
+    /// The joltage counters after pressing each button `presses[button]` times.
+    fn joltage_after(&self, presses: &[usize]) -> Vec<usize> {
+        let mut joltage = vec![0; self.joltage_requirements.len()];
+        for (wiring, &count) in self.button_wiring.iter().zip(presses) {
+            for &counter in wiring {
+                joltage[counter] += count;
+            }
+        }
+        joltage
+    }
+
+    /// Presses as `(0,2)x3 (1)x1`, skipping buttons that aren't pressed.
+    fn describe_plan(&self, presses: &[usize]) -> String {
+        let pressed: Vec<String> = self
+            .button_wiring
+            .iter()
+            .zip(presses)
+            .filter(|(_, count)| **count > 0)
+            .map(|(wiring, count)| {
+                let counters: Vec<String> = wiring.iter().map(|c| c.to_string()).collect();
+                format!("({})x{}", counters.join(","), count)
+            })
+            .collect();
+        if pressed.is_empty() {
+            "no presses".to_string()
+        } else {
+            pressed.join(" ")
+        }
+    }
+
+    /// Presses per button for the lights, checked by replaying them.
+    fn lights_plan(&self, deadline: &Deadline) -> Result<Vec<usize>, MachineError> {
+        let presses = self.solve_lights(deadline)?;
+        assert_eq!(
+            self.lights_after(&presses),
+            self.indicator_lights,
+            "Pressing {} should turn on the lights",
+            self.describe_plan(&presses)
+        );
+        Ok(presses)
     }
 
     fn build_joltage_matrix(&self) -> Vec<Vec<i64>> {
@@ -138,7 +533,7 @@ impl Machine {
     /// Returns the total button presses if a valid non-negative solution exists.
     fn try_solve_buttons(
         &self,
-        matrix: &Vec<Vec<i64>>,
+        matrix: &[Vec<i64>],
         pivot_cols: &[usize],
         free_cols: &[usize],
         free_vals: &[i64],
@@ -193,7 +588,7 @@ impl Machine {
     /// and check if the solution is valid (non-negative integers).
     fn solve_from_eliminated(
         &self,
-        matrix: &Vec<Vec<i64>>,
+        matrix: &[Vec<i64>],
         pivot_cols: &[usize],
     ) -> Option<usize> {
         let num_buttons = self.button_wiring.len();
@@ -219,11 +614,11 @@ impl Machine {
 
             // Increment free_vals like a multi-digit counter.
             let mut carry = true;
-            for i in 0..num_free {
+            for free_val in free_vals.iter_mut() {
                 if carry {
-                    free_vals[i] += 1;
-                    if free_vals[i] > max_val {
-                        free_vals[i] = 0;
+                    *free_val += 1;
+                    if *free_val > max_val {
+                        *free_val = 0;
                     } else {
                         carry = false;
                     }
@@ -236,12 +631,53 @@ impl Machine {
         best
     }
 
-    fn set_joltage(&self) -> usize {
-        let mut matrix = self.build_joltage_matrix();
-        let pivot_cols = gaussian_eliminate(&mut matrix);
+    /// Upper bound on each button's presses: it raises every counter it is wired to,
+    /// so it can't be pressed more often than the smallest of their targets.
+    fn button_bounds(&self) -> Vec<i64> {
+        self.button_wiring
+            .iter()
+            .map(|wiring| {
+                wiring
+                    .iter()
+                    .map(|&counter| self.joltage_requirements[counter] as i64)
+                    .min()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    /// The fewest presses of each button that bring every counter to its requirement.
+    fn solve_joltage(&self, deadline: &Deadline) -> Result<Vec<usize>, MachineError> {
+        self.check_wiring(self.joltage_requirements.len())?;
+        let rref = Rref::new(&self.build_joltage_matrix())?;
 
-        self.solve_from_eliminated(&matrix, &pivot_cols)
-            .expect("Should have a solution")
+        let solution = match JoltageSearch::new(&rref, &self.button_bounds(), *deadline)? {
+            Some(search) => search.solve()?,
+            None => None,
+        };
+
+        // The exhaustive enumerator is exact but exponential in the number of free
+        // variables, so only use it as an oracle when that is small. It also only looks
+        // at the pivot rows, so can't spot an inconsistent system.
+        if cfg!(debug_assertions) && rref.is_consistent() && rref.free_cols().len() <= 2 {
+            let total = solution.as_ref().map(|presses| presses.iter().sum::<i64>() as usize);
+            assert_eq!(total, self.solve_from_eliminated(rref.rows(), rref.pivot_cols()));
+        }
+
+        let presses = solution.ok_or(MachineError::NoJoltageSolution)?;
+        Ok(presses.into_iter().map(|count| count as usize).collect())
+    }
+
+    /// Presses per button for the joltage counters, checked by replaying them.
+    fn joltage_plan(&self, deadline: &Deadline) -> Result<Vec<usize>, MachineError> {
+        let presses = self.solve_joltage(deadline)?;
+        assert_eq!(
+            self.joltage_after(&presses),
+            self.joltage_requirements,
+            "Pressing {} should reach the joltage requirements",
+            self.describe_plan(&presses)
+        );
+        Ok(presses)
     }
 }
 
@@ -277,34 +713,101 @@ fn parse_input_line(line: &str) -> Machine {
     }
 }
 
-fn part1(input: &str) -> usize {
+/// Hands a timeout back to the job runner as [`Expired`], so it's counted as one.
+fn timed_out_separately(
+    plan: Result<Vec<usize>, MachineError>,
+) -> Result<Result<Vec<usize>, MachineError>, Expired> {
+    match plan {
+        Err(MachineError::TimedOut) => Err(Expired),
+        plan => Ok(plan),
+    }
+}
+
+/// Adds up the plans, printing them with their timings if asked, and collects the machines
+/// without one.
+fn summarise(
+    input: &str,
+    machines: &[Machine],
+    reports: Vec<JobReport<Result<Vec<usize>, MachineError>>>,
+    show_plans: bool,
+) -> PartResult {
+    let mut result = PartResult { presses: 0, failures: Vec::new() };
+    for (i, ((line, machine), report)) in input.lines().zip(machines).zip(reports).enumerate() {
+        let plan = match report.outcome {
+            Outcome::Done(plan) => plan,
+            Outcome::TimedOut => Err(MachineError::TimedOut),
+        };
+        match plan {
+            Ok(plan) => {
+                if show_plans {
+                    eprintln!(
+                        "{} => {} ({:.1?})",
+                        line,
+                        machine.describe_plan(&plan),
+                        report.elapsed
+                    );
+                }
+                result.presses += plan.iter().sum::<usize>();
+            }
+            Err(error) => result.failures.push(Failure { line: i + 1, error }),
+        }
+    }
+    result
+}
+
+fn part1(input: &str, runner: &JobRunner, show_plans: bool) -> PartResult {
     let machines: Vec<Machine> = input
         .lines()
-        .map(|line| parse_input_line(line))
+        .map(parse_input_line)
         .collect();
 
-    let mut presses = 0;
-    for machine in machines {
-        presses += machine.turn_on_lights();
-    }
-    presses
+    let reports = runner
+        .run_cached(&machines, |m, deadline| timed_out_separately(m.lights_plan(deadline)));
+    summarise(input, &machines, reports, show_plans)
 }
 
-fn part2(input: &str) -> usize {
+fn part2(input: &str, runner: &JobRunner, show_plans: bool) -> PartResult {
     let machines: Vec<Machine> = input
         .lines()
-        .map(|line| parse_input_line(line))
+        .map(parse_input_line)
         .collect();
-    
-    machines
-        .par_iter()
-        .map(|m| m.set_joltage())
-        .sum()
+
+    let reports = runner
+        .run_cached(&machines, |m, deadline| timed_out_separately(m.joltage_plan(deadline)));
+    summarise(input, &machines, reports, show_plans)
+}
+
+/// Prints the total, then any machines that were left out of it.
+fn report(result: PartResult) {
+    println!("{}", result.presses);
+    for failure in &result.failures {
+        eprintln!("Line {}: {}", failure.line, failure.error);
+    }
+    if !result.failures.is_empty() {
+        eprintln!("{} machine(s) skipped, the total only covers the rest", result.failures.len());
+    }
 }
 
 fn main() {
     let input = include_str!("../../../../inputs/day10.txt");
 
-    println!("{}", part1(input));
-    println!("{}", part2(input));
-}
\ No newline at end of file
+    let args: Vec<String> = std::env::args().collect();
+    let option = |name: &str| option_value(&args, name);
+
+    // `--plans` prints each machine's button presses and how long it took.
+    let show_plans = args.iter().any(|arg| arg == "--plans");
+
+    // `--threads N`, `--timeout SECONDS` per machine, and `--progress` for a status line.
+    let runner = |label: &str| JobRunner {
+        threads: option("--threads")
+            .map(|n| n.parse().expect("Thread count should be a number"))
+            .unwrap_or(0),
+        timeout: option("--timeout").map(|secs| {
+            Duration::from_secs_f64(secs.parse().expect("Timeout should be a number of seconds"))
+        }),
+        progress: args.iter().any(|arg| arg == "--progress").then(|| label.to_string()),
+    };
+
+    report(part1(input, &runner("Part 1"), show_plans));
+    report(part2(input, &runner("Part 2"), show_plans));
+}
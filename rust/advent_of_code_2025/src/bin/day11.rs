@@ -1,106 +1,277 @@
-use std::collections::HashMap;
-
-fn parse_line(line: &str) -> HashMap<String, Vec<String>> {
-    let tokens: Vec<&str> = line.split_whitespace().collect();
-    assert!(tokens[0].ends_with(":"));
-
-    let mut map = HashMap::new();
-    map.insert(
-        tokens[0].trim_matches(':').to_string(),
-        tokens[1..].iter().map(|s| s.to_string()).collect(),
-    );
-    map
-}
-
-fn count_paths(
-    node: &str,
-    graph: &HashMap<String, Vec<String>>,
-    memo: &mut HashMap<String, usize>,
-) -> usize {
-    if node == "out" {
-        return 1;
-    }
+use advent_of_code_2025::counting::{CountMode, Counting, WithCounting};
+use advent_of_code_2025::graph::{Graph, GraphBuilder, NodeId};
+use advent_of_code_2025::paths::{PathCounter, PathQuery};
+use advent_of_code_2025::option_value;
+use std::fmt::{self, Write};
+use std::fs;
+
+fn parse_graph(input: &str) -> Graph {
+    let mut builder = GraphBuilder::new();
+    for line in input.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        assert!(tokens[0].ends_with(":"));
 
-    if let Some(&count) = memo.get(node) {
-        return count;
+        let device = tokens[0].trim_matches(':');
+        builder.node(device);
+        for output in &tokens[1..] {
+            builder.edge(device, output);
+        }
     }
+    builder.build()
+}
+
+fn path_counter(graph: &Graph) -> PathCounter<'_> {
+    PathCounter::new(graph).unwrap_or_else(|err| panic!("{}", err))
+}
 
-    let count = match graph.get(node) {
-        Some(children) => children
-            .iter()
-            .map(|child| count_paths(child, graph, memo))
-            .sum(),
-        None => 0,
+/// Counts paths between named devices that pass through every `required` one. Paths
+/// can't start, end or pass through a device missing from the graph.
+fn count_named_paths<C: Counting>(
+    graph: &Graph,
+    from: &str,
+    to: &str,
+    required: &[&str],
+    counting: &C,
+) -> C::Value {
+    let (Some(source), Some(sink)) = (graph.id(from), graph.id(to)) else {
+        return counting.zero();
     };
+    let Some(required) = required.iter().map(|name| graph.id(name)).collect() else {
+        return counting.zero();
+    };
+    let query = PathQuery {
+        required,
+        ..PathQuery::new(source, sink)
+    };
+    path_counter(graph)
+        .count(&query, counting)
+        .unwrap_or_else(|err| panic!("{}", err))
+}
 
-    memo.insert(node.to_string(), count);
-    count
+fn part1<C: Counting>(input: &str, counting: &C) -> C::Value {
+    let graph = parse_graph(input);
+    count_named_paths(&graph, "you", "out", &[], counting)
 }
 
-fn part1(input: &str) -> usize {
-    let graph =
-        input
-            .lines()
-            .map(|line| parse_line(line))
-            .fold(HashMap::new(), |mut acc, paths| {
-                acc.extend(paths);
-                acc
-            });
+fn part2<C: Counting>(input: &str, counting: &C) -> C::Value {
+    let graph = parse_graph(input);
+    count_named_paths(&graph, "svr", "out", &["dac", "fft"], counting)
+}
 
-    let mut memo = HashMap::new();
-    count_paths("you", &graph, &mut memo)
+/// Which files to export the device graph to.
+struct ExportOptions {
+    dot_path: Option<String>,
+    json_path: Option<String>,
+    /// Export only devices on some path from `svr` to `out`.
+    on_path_only: bool,
 }
 
-fn count_paths_with_required_nodes(
-    node: &str,
-    graph: &HashMap<String, Vec<String>>,
-    visited_dac: bool,
-    visited_fft: bool,
-    memo: &mut HashMap<(String, bool, bool), usize>,
-) -> usize {
-    let visited_dac = visited_dac || node == "dac";
-    let visited_fft = visited_fft || node == "fft";
+/// A graph to export, with each device's `svr` to `out` path counts.
+struct AnnotatedGraph<V> {
+    graph: Graph,
+    from_source: Vec<Option<V>>,
+    to_sink: Vec<Option<V>>,
+}
 
-    if node == "out" {
-        return if visited_dac && visited_fft { 1 } else { 0 };
+impl<V: fmt::Display> AnnotatedGraph<V> {
+    fn new<C: Counting<Value = V>>(input: &str, counting: &C, on_path_only: bool) -> Self {
+        let annotate = |graph: Graph| {
+            let source = graph.id("svr").expect("Should have a svr device");
+            let sink = graph.id("out").expect("Should have an out device");
+            let counter = path_counter(&graph);
+            let from_source = counter.paths_from(source, counting);
+            let to_sink = counter.paths_to(sink, counting);
+            AnnotatedGraph {
+                from_source: from_source.unwrap_or_else(|err| panic!("{}", err)),
+                to_sink: to_sink.unwrap_or_else(|err| panic!("{}", err)),
+                graph,
+            }
+        };
+
+        let annotated = annotate(parse_graph(input));
+        if !on_path_only {
+            return annotated;
+        }
+        // Every svr to out path stays inside the subgraph, so its counts are the same.
+        let on_path = |node: NodeId| {
+            annotated.from_source[node as usize].is_some()
+                && annotated.to_sink[node as usize].is_some()
+        };
+        annotate(annotated.graph.subgraph(on_path))
+    }
+
+    /// A count for display, with unreachable shown as zero.
+    fn count(counts: &[Option<V>], node: NodeId) -> String {
+        counts[node as usize]
+            .as_ref()
+            .map_or("0".to_string(), |count| count.to_string())
     }
 
-    let key = (node.to_string(), visited_dac, visited_fft);
-    if let Some(&count) = memo.get(&key) {
-        return count;
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph devices {\n");
+        for node in self.graph.nodes() {
+            let name = self.graph.name(node);
+            let style = if name == "svr" || name == "out" { ", style=bold" } else { "" };
+            writeln!(
+                dot,
+                r#"    {} [label="{}\nfrom svr: {}\nto out: {}"{}];"#,
+                quote(name),
+                escape(name),
+                Self::count(&self.from_source, node),
+                Self::count(&self.to_sink, node),
+                style
+            )
+            .unwrap();
+        }
+        for from in self.graph.nodes() {
+            for &to in self.graph.successors(from) {
+                let (from, to) = (self.graph.name(from), self.graph.name(to));
+                writeln!(dot, "    {} -> {};", quote(from), quote(to)).unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
     }
 
-    let count = match graph.get(node) {
-        Some(children) => children
-            .iter()
-            .map(|child| {
-                count_paths_with_required_nodes(child, graph, visited_dac, visited_fft, memo)
+    fn to_json(&self) -> String {
+        let nodes: Vec<String> = self
+            .graph
+            .nodes()
+            .map(|node| {
+                let outputs: Vec<String> = self
+                    .graph
+                    .successors(node)
+                    .iter()
+                    .map(|&next| quote(self.graph.name(next)))
+                    .collect();
+                format!(
+                    concat!(
+                        r#"    {{"name": {}, "paths_from_source": {}, "#,
+                        r#""paths_to_sink": {}, "outputs": [{}]}}"#
+                    ),
+                    quote(self.graph.name(node)),
+                    Self::count(&self.from_source, node),
+                    Self::count(&self.to_sink, node),
+                    outputs.join(", ")
+                )
             })
-            .sum(),
-        None => 0,
+            .collect();
+        format!(
+            "{{\n  \"source\": \"svr\",\n  \"sink\": \"out\",\n  \"nodes\": [\n{}\n  ]\n}}\n",
+            nodes.join(",\n")
+        )
+    }
+}
+
+/// Backslash-escapes a name for use inside a DOT or JSON string.
+fn escape(name: &str) -> String {
+    name.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", escape(name))
+}
+
+fn export<C: Counting>(input: &str, counting: &C, options: &ExportOptions) {
+    if options.dot_path.is_none() && options.json_path.is_none() {
+        return;
+    }
+    let annotated = AnnotatedGraph::new(input, counting, options.on_path_only);
+    let write = |path: &str, contents: String| {
+        fs::write(path, contents).unwrap_or_else(|err| panic!("Failed to write {}: {}", path, err));
     };
+    if let Some(path) = &options.dot_path {
+        write(path, annotated.to_dot());
+    }
+    if let Some(path) = &options.json_path {
+        write(path, annotated.to_json());
+    }
+}
 
-    memo.insert(key, count);
-    count
+/// Everything day11 prints or writes, for whichever counting strategy was chosen.
+struct Run<'a> {
+    input: &'a str,
+    query: Option<CustomQuery>,
+    export_options: ExportOptions,
 }
 
-fn part2(input: &str) -> usize {
-    let graph =
-        input
-            .lines()
-            .map(|line| parse_line(line))
-            .fold(HashMap::new(), |mut acc, paths| {
-                acc.extend(paths);
-                acc
-            });
+impl WithCounting for Run<'_> {
+    type Output = ();
 
-    let mut memo = HashMap::new();
-    count_paths_with_required_nodes("svr", &graph, false, false, &mut memo)
+    fn run<C: Counting>(self, counting: &C) {
+        run(self.input, counting, self.query.as_ref(), &self.export_options);
+    }
+}
+
+fn run<C: Counting>(
+    input: &str,
+    counting: &C,
+    query: Option<&CustomQuery>,
+    export_options: &ExportOptions,
+) {
+    println!("{}", part1(input, counting));
+    println!("{}", part2(input, counting));
+
+    if let Some(custom) = query {
+        let graph = parse_graph(input);
+        let id = |name: &String| {
+            graph.id(name).unwrap_or_else(|| panic!("No device named {}", name))
+        };
+        let query = PathQuery {
+            required: custom.via.iter().map(id).collect(),
+            avoided: custom.avoid.iter().map(id).collect(),
+            ordered: custom.ordered,
+            ..PathQuery::new(id(&custom.from), id(&custom.to))
+        };
+        let count = path_counter(&graph)
+            .count(&query, counting)
+            .unwrap_or_else(|err| panic!("{}", err));
+        println!("{}", count);
+    }
+
+    export(input, counting, export_options);
+}
+
+/// A path count asked for on the command line, by device name.
+struct CustomQuery {
+    from: String,
+    to: String,
+    via: Vec<String>,
+    avoid: Vec<String>,
+    ordered: bool,
 }
 
 fn main() {
     let input = include_str!("../../../../inputs/day11.txt");
 
-    println!("{}", part1(input));
-    println!("{}", part2(input));
+    let args: Vec<String> = std::env::args().collect();
+    let option = |name: &str| option_value(&args, name);
+
+    // `--count u128|big|mod:<p>` picks how paths are counted.
+    let count_mode: CountMode = option("--count")
+        .map(|mode| mode.parse().unwrap_or_else(|err| panic!("{}", err)))
+        .unwrap_or_default();
+
+    // `--from A --to B` also counts paths from A to B, optionally `--via C,D` (in that
+    // order with `--ordered`) and `--avoid E,F`.
+    let list = |name: &str| -> Vec<String> {
+        option(name).map_or(Vec::new(), |names| names.split(',').map(str::to_string).collect())
+    };
+    let query = option("--from").zip(option("--to")).map(|(from, to)| CustomQuery {
+        from,
+        to,
+        via: list("--via"),
+        avoid: list("--avoid"),
+        ordered: args.iter().any(|arg| arg == "--ordered"),
+    });
+
+    // `--dot <path>` and `--json <path>` export the device graph, annotated with path
+    // counts from `svr` and to `out`; `--on-path` keeps only devices between the two.
+    let export_options = ExportOptions {
+        dot_path: option("--dot"),
+        json_path: option("--json"),
+        on_path_only: args.iter().any(|arg| arg == "--on-path"),
+    };
+
+    count_mode.with(Run { input, query, export_options });
 }
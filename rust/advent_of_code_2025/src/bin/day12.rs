@@ -1,6 +1,5 @@
-use std::str::Lines;
-
 struct Shape {
+    #[allow(dead_code)]
     shape_num: u32,
     grid: Vec<Vec<char>>,
 }
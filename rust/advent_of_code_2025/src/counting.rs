@@ -0,0 +1,225 @@
+use std::fmt;
+
+/// Returned when a count no longer fits in the counter's value type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CountOverflow;
+
+impl fmt::Display for CountOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Count overflowed")
+    }
+}
+
+/// A strategy for accumulating counts that can grow exponentially, such as
+/// timelines through a manifold or paths through a graph.
+///
+/// The strategy is a value rather than just a type so that it can carry
+/// runtime parameters, like the modulus for [`Modulo`].
+pub trait Counting {
+    type Value: Clone + fmt::Display;
+
+    /// The count of nothing.
+    fn zero(&self) -> Self::Value;
+
+    /// The count of a single item.
+    fn one(&self) -> Self::Value;
+
+    /// Adds two counts, failing if the result is not representable.
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Result<Self::Value, CountOverflow>;
+}
+
+/// Exact counting in a `u128`, reporting overflow rather than wrapping.
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::counting::{CheckedU128, CountOverflow, Counting};
+/// let counting = CheckedU128;
+/// assert_eq!(counting.add(&2, &3), Ok(5));
+/// assert_eq!(counting.add(&u128::MAX, &counting.one()), Err(CountOverflow));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CheckedU128;
+
+impl Counting for CheckedU128 {
+    type Value = u128;
+
+    fn zero(&self) -> u128 {
+        0
+    }
+
+    fn one(&self) -> u128 {
+        1
+    }
+
+    fn add(&self, a: &u128, b: &u128) -> Result<u128, CountOverflow> {
+        a.checked_add(*b).ok_or(CountOverflow)
+    }
+}
+
+/// Counting modulo a user-supplied modulus (typically a large prime).
+/// Never overflows, but only the residue of the true count is known.
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::counting::{Counting, Modulo};
+/// let counting = Modulo::new(7);
+/// assert_eq!(counting.add(&5, &4), Ok(2));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Modulo {
+    modulus: u64,
+}
+
+impl Modulo {
+    /// # Panics
+    /// If `modulus` is zero.
+    pub fn new(modulus: u64) -> Self {
+        assert!(modulus > 0, "Modulus must be positive");
+        Modulo { modulus }
+    }
+}
+
+impl Counting for Modulo {
+    type Value = u64;
+
+    fn zero(&self) -> u64 {
+        0
+    }
+
+    fn one(&self) -> u64 {
+        1 % self.modulus
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> Result<u64, CountOverflow> {
+        // Widen so the sum of two residues cannot overflow.
+        Ok(((*a as u128 + *b as u128) % self.modulus as u128) as u64)
+    }
+}
+
+/// Exact arbitrary-precision counting.
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::counting::{BigCount, Counting};
+/// let counting = BigCount;
+/// let max = num_bigint::BigUint::from(u128::MAX);
+/// let sum = counting.add(&max, &counting.one()).unwrap();
+/// assert_eq!(sum.to_string(), "340282366920938463463374607431768211456");
+/// ```
+#[cfg(feature = "bigint")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BigCount;
+
+#[cfg(feature = "bigint")]
+impl Counting for BigCount {
+    type Value = num_bigint::BigUint;
+
+    fn zero(&self) -> num_bigint::BigUint {
+        num_bigint::BigUint::ZERO
+    }
+
+    fn one(&self) -> num_bigint::BigUint {
+        num_bigint::BigUint::from(1u8)
+    }
+
+    fn add(
+        &self,
+        a: &num_bigint::BigUint,
+        b: &num_bigint::BigUint,
+    ) -> Result<num_bigint::BigUint, CountOverflow> {
+        Ok(a + b)
+    }
+}
+
+/// Returned when a [`CountMode`] can't be parsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseCountModeError {
+    Unknown(String),
+    BadModulus(String),
+}
+
+impl fmt::Display for ParseCountModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseCountModeError::Unknown(mode) => write!(f, "Unknown count mode: {}", mode),
+            ParseCountModeError::BadModulus(modulus) => {
+                write!(f, "Modulus should be a positive number: {}", modulus)
+            }
+        }
+    }
+}
+
+/// Work to do with whichever [`Counting`] strategy a [`CountMode`] picks.
+///
+/// A trait rather than a closure because the work is generic over the strategy.
+pub trait WithCounting {
+    type Output;
+
+    fn run<C: Counting>(self, counting: &C) -> Self::Output;
+}
+
+/// A [`Counting`] strategy chosen at runtime, parsed from `u128`, `big` (with the
+/// `bigint` feature) or `mod:<p>`.
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::counting::{CountMode, Counting, WithCounting};
+/// struct Overflowing;
+/// impl WithCounting for Overflowing {
+///     type Output = bool;
+///     fn run<C: Counting>(self, counting: &C) -> bool {
+///         let mut total = counting.one();
+///         for _ in 0..200 {
+///             match counting.add(&total, &total) {
+///                 Ok(doubled) => total = doubled,
+///                 Err(_) => return true,
+///             }
+///         }
+///         false
+///     }
+/// }
+///
+/// let mode: CountMode = "u128".parse().unwrap();
+/// assert!(mode.with(Overflowing));
+/// let mode: CountMode = "mod:1000000007".parse().unwrap();
+/// assert_eq!(mode, CountMode::Modulo(1_000_000_007));
+/// assert!(!mode.with(Overflowing));
+/// assert!("mod:0".parse::<CountMode>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CountMode {
+    #[default]
+    CheckedU128,
+    #[cfg(feature = "bigint")]
+    Big,
+    Modulo(u64),
+}
+
+impl std::str::FromStr for CountMode {
+    type Err = ParseCountModeError;
+
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        match mode.split_once(':') {
+            Some(("mod", modulus)) => match modulus.parse() {
+                Ok(modulus) if modulus > 0 => Ok(CountMode::Modulo(modulus)),
+                _ => Err(ParseCountModeError::BadModulus(modulus.to_string())),
+            },
+            #[cfg(feature = "bigint")]
+            None if mode == "big" => Ok(CountMode::Big),
+            None if mode == "u128" => Ok(CountMode::CheckedU128),
+            _ => Err(ParseCountModeError::Unknown(mode.to_string())),
+        }
+    }
+}
+
+impl CountMode {
+    /// Runs `work` with this mode's strategy.
+    pub fn with<W: WithCounting>(self, work: W) -> W::Output {
+        match self {
+            CountMode::CheckedU128 => work.run(&CheckedU128),
+            #[cfg(feature = "bigint")]
+            CountMode::Big => work.run(&BigCount),
+            CountMode::Modulo(modulus) => work.run(&Modulo::new(modulus)),
+        }
+    }
+}
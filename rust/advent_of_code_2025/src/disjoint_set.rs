@@ -0,0 +1,230 @@
+/// A single write to the forest, recorded so it can be undone.
+#[derive(Clone, Copy, Debug)]
+struct Change {
+    node: usize,
+    old_parent: usize,
+    old_size: usize,
+}
+
+/// A point in a [`DisjointSet`]'s history that can be rolled back to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Snapshot {
+    // How many times the set had been committed when the snapshot was taken.
+    commits: usize,
+    history_len: usize,
+    component_count: usize,
+}
+
+/// Disjoint-set forest (union-find) over the elements `0..n`, with path
+/// compression and union by size.
+///
+/// The number of components is tracked as unions happen, so asking "is
+/// everything connected yet?" is O(1).
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::DisjointSet;
+/// let mut set = DisjointSet::new(5);
+/// assert!(set.union(0, 1));
+/// assert!(set.union(1, 2));
+/// assert!(!set.union(0, 2)); // Already connected.
+/// assert_eq!(set.component_count(), 3);
+/// assert_eq!(set.size_of(2), 3);
+/// assert_eq!(set.members(1), vec![0, 1, 2]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    component_count: usize,
+    // Only populated between taking a snapshot and the next commit.
+    history: Vec<Change>,
+    recording: bool,
+    commits: usize,
+}
+
+impl DisjointSet {
+    /// Creates `n` singleton components.
+    pub fn new(n: usize) -> Self {
+        DisjointSet {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            component_count: n,
+            history: Vec::new(),
+            recording: false,
+            commits: 0,
+        }
+    }
+
+    /// Number of elements in the set.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Returns `true` if the set has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    fn set_parent(&mut self, node: usize, parent: usize) {
+        if self.recording {
+            self.history.push(Change {
+                node,
+                old_parent: self.parent[node],
+                old_size: self.size[node],
+            });
+        }
+        self.parent[node] = parent;
+    }
+
+    fn set_size(&mut self, node: usize, size: usize) {
+        if self.recording {
+            self.history.push(Change {
+                node,
+                old_parent: self.parent[node],
+                old_size: self.size[node],
+            });
+        }
+        self.size[node] = size;
+    }
+
+    /// Returns the representative of the component containing `x`,
+    /// compressing the path to it along the way.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Point everything on the path straight at the root.
+        let mut node = x;
+        while node != root {
+            let next = self.parent[node];
+            if next != root {
+                self.set_parent(node, root);
+            }
+            node = next;
+        }
+
+        root
+    }
+
+    /// Merges the components containing `x` and `y`.
+    ///
+    /// # Returns
+    /// `true` if they were separate components, `false` if already connected
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let root_x = self.find(x);
+        let root_y = self.find(y);
+
+        if root_x == root_y {
+            return false;
+        }
+
+        // Union by size: hang the smaller tree under the larger.
+        let (small, large) = if self.size[root_x] < self.size[root_y] {
+            (root_x, root_y)
+        } else {
+            (root_y, root_x)
+        };
+        self.set_parent(small, large);
+        self.set_size(large, self.size[large] + self.size[small]);
+        self.component_count -= 1;
+        true
+    }
+
+    /// Returns `true` if `x` and `y` are in the same component.
+    pub fn connected(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Number of components, maintained incrementally.
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    /// Size of the component containing `x`.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Sizes of every component, in order of their representatives. O(n).
+    pub fn component_sizes(&mut self) -> Vec<usize> {
+        let roots: Vec<usize> = (0..self.len()).filter(|&i| self.find(i) == i).collect();
+        roots.into_iter().map(|root| self.size[root]).collect()
+    }
+
+    /// Elements of the component containing `x`, in ascending order. O(n).
+    pub fn members(&mut self, x: usize) -> Vec<usize> {
+        let root = self.find(x);
+        (0..self.len()).filter(|&i| self.find(i) == root).collect()
+    }
+
+    /// Marks the current state so it can be restored with [`rollback`](Self::rollback).
+    /// Every change is recorded from then on, until [`commit`](Self::commit) is called.
+    ///
+    /// # Example
+    /// ```
+    /// use advent_of_code_2025::DisjointSet;
+    /// let mut set = DisjointSet::new(4);
+    /// set.union(0, 1);
+    /// let snapshot = set.snapshot();
+    /// set.union(2, 3);
+    /// set.union(0, 3);
+    /// assert_eq!(set.component_count(), 1);
+    /// set.rollback(snapshot);
+    /// assert_eq!(set.component_count(), 3);
+    /// assert!(set.connected(0, 1));
+    /// assert!(!set.connected(2, 3));
+    /// ```
+    pub fn snapshot(&mut self) -> Snapshot {
+        self.recording = true;
+        Snapshot {
+            commits: self.commits,
+            history_len: self.history.len(),
+            component_count: self.component_count,
+        }
+    }
+
+    /// Undoes every change made since `snapshot` was taken, including path
+    /// compression. Snapshots taken after `snapshot` are invalidated.
+    ///
+    /// # Panics
+    /// If `snapshot` is from before the last [`commit`](Self::commit), or was
+    /// invalidated by rolling back past it.
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        assert_eq!(snapshot.commits, self.commits, "Snapshot was taken before a commit");
+        assert!(
+            snapshot.history_len <= self.history.len(),
+            "Snapshot is newer than the current history"
+        );
+        while self.history.len() > snapshot.history_len {
+            let change = self.history.pop().unwrap();
+            self.parent[change.node] = change.old_parent;
+            self.size[change.node] = change.old_size;
+        }
+        self.component_count = snapshot.component_count;
+    }
+
+    /// Keeps every change made so far, stops recording new ones and frees the
+    /// history. Every snapshot taken so far is invalidated.
+    ///
+    /// # Example
+    /// ```
+    /// use advent_of_code_2025::DisjointSet;
+    /// let mut set = DisjointSet::new(3);
+    /// let snapshot = set.snapshot();
+    /// set.union(0, 1);
+    /// set.commit();
+    /// set.union(1, 2);
+    /// assert_eq!(set.component_count(), 1);
+    /// let result = std::panic::catch_unwind(move || set.rollback(snapshot));
+    /// assert!(result.is_err());
+    /// ```
+    pub fn commit(&mut self) {
+        self.history = Vec::new();
+        self.recording = false;
+        self.commits += 1;
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+
+/// Returned when an intermediate value no longer fits in an `i64`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ArithmeticOverflow;
+
+impl fmt::Display for ArithmeticOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Arithmetic overflowed during elimination")
+    }
+}
+
+/// Greatest common divisor, always non-negative.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+/// Least common multiple of two non-zero values, failing if it doesn't fit in an `i64`.
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::elimination::{checked_lcm, gcd, ArithmeticOverflow};
+/// assert_eq!(gcd(-12, 18), 6);
+/// assert_eq!(checked_lcm(4, 6), Ok(12));
+/// assert_eq!(checked_lcm(i64::MAX, 2), Err(ArithmeticOverflow));
+/// ```
+pub fn checked_lcm(a: i64, b: i64) -> Result<i64, ArithmeticOverflow> {
+    (a / gcd(a, b)).checked_mul(b).ok_or(ArithmeticOverflow)
+}
+
+/// Divides `values` by the GCD of its entries, leaving the signs alone.
+fn normalise(values: &mut [i64]) {
+    let divisor = values.iter().fold(0, |a, &b| gcd(a, b));
+    if divisor > 1 {
+        values.iter_mut().for_each(|value| *value /= divisor);
+    }
+}
+
+/// An integer linear system `A x = b` in reduced row echelon form, computed exactly.
+///
+/// Elimination is fraction-free: rows are combined by cross-multiplying with the pivot
+/// (after dividing both multipliers by their GCD) and each row is then divided by the GCD
+/// of its entries, so values stay as small as the system allows. Every step is checked,
+/// and overflow is reported instead of silently producing a wrong answer.
+///
+/// Pivots are positive but not necessarily 1: pivot row `i` reads
+/// `rows[i][pivot_cols[i]] * x[pivot_cols[i]] + sum(rows[i][f] * x[f]) = rows[i][variables]`
+/// over the free columns `f`.
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::elimination::Rref;
+/// // x + y + z = 6, x - y = 0, as an augmented matrix.
+/// let rref = Rref::new(&[vec![1, 1, 1, 6], vec![1, -1, 0, 0]]).unwrap();
+/// assert_eq!(rref.pivot_cols(), &[0, 1]);
+/// assert_eq!(rref.free_cols(), vec![2]);
+/// assert_eq!(rref.rows(), &[vec![2, 0, 1, 6], vec![0, 2, 1, 6]]);
+/// assert!(rref.is_consistent());
+/// assert_eq!(rref.null_space().unwrap(), vec![vec![-1, -1, 2]]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rref {
+    rows: Vec<Vec<i64>>,
+    variables: usize,
+    pivot_cols: Vec<usize>,
+}
+
+impl Rref {
+    /// Eliminates an augmented matrix `[A | b]`: every column but the last is a variable.
+    ///
+    /// # Panics
+    /// If the rows are empty or of differing lengths.
+    pub fn new(augmented: &[Vec<i64>]) -> Result<Self, ArithmeticOverflow> {
+        let width = augmented.first().map_or(0, |row| row.len());
+        assert!(width > 0, "Expected at least one column");
+        assert!(
+            augmented.iter().all(|row| row.len() == width),
+            "Expected every row to have {} columns",
+            width
+        );
+
+        let mut rows = augmented.to_vec();
+        rows.iter_mut().for_each(|row| normalise(row));
+        let variables = width - 1;
+        let mut pivot_cols = Vec::new();
+
+        for col in 0..variables {
+            let pivot_row = pivot_cols.len();
+            // The smallest pivot keeps the cross-multiplied rows smallest.
+            let Some(best) = (pivot_row..rows.len())
+                .filter(|&row| rows[row][col] != 0)
+                .min_by_key(|&row| rows[row][col].unsigned_abs())
+            else {
+                continue;
+            };
+            rows.swap(pivot_row, best);
+            if rows[pivot_row][col] < 0 {
+                for value in rows[pivot_row].iter_mut() {
+                    *value = value.checked_neg().ok_or(ArithmeticOverflow)?;
+                }
+            }
+            pivot_cols.push(col);
+
+            let pivot = rows[pivot_row].clone();
+            for (index, row) in rows.iter_mut().enumerate() {
+                if index == pivot_row || row[col] == 0 {
+                    continue;
+                }
+                // row * (p / g) - pivot * (v / g) clears the column.
+                let divisor = gcd(pivot[col], row[col]);
+                let (row_scale, pivot_scale) = (pivot[col] / divisor, row[col] / divisor);
+                for (value, &pivot_value) in row.iter_mut().zip(&pivot) {
+                    *value = value
+                        .checked_mul(row_scale)
+                        .zip(pivot_value.checked_mul(pivot_scale))
+                        .and_then(|(a, b)| a.checked_sub(b))
+                        .ok_or(ArithmeticOverflow)?;
+                }
+                normalise(row);
+            }
+        }
+
+        Ok(Rref {
+            rows,
+            variables,
+            pivot_cols,
+        })
+    }
+
+    /// The reduced rows, pivot rows first, each including its right-hand side.
+    pub fn rows(&self) -> &[Vec<i64>] {
+        &self.rows
+    }
+
+    /// Number of variables (columns of `A`).
+    pub fn variables(&self) -> usize {
+        self.variables
+    }
+
+    /// Column of each pivot row's pivot, ascending.
+    pub fn pivot_cols(&self) -> &[usize] {
+        &self.pivot_cols
+    }
+
+    /// Columns without a pivot: variables that can be chosen freely.
+    pub fn free_cols(&self) -> Vec<usize> {
+        (0..self.variables)
+            .filter(|col| !self.pivot_cols.contains(col))
+            .collect()
+    }
+
+    /// Returns `false` if some row reduced to `0 = nonzero`, so `A x = b` has no solution
+    /// even over the rationals.
+    pub fn is_consistent(&self) -> bool {
+        self.rows[self.pivot_cols.len()..]
+            .iter()
+            .all(|row| row[self.variables] == 0)
+    }
+
+    /// An integer basis of the null space of `A`, one vector per free column.
+    ///
+    /// The vector for free column `f` is the smallest integer multiple of "set `x[f]` to 1,
+    /// the other free variables to 0, and solve for the pivots".
+    pub fn null_space(&self) -> Result<Vec<Vec<i64>>, ArithmeticOverflow> {
+        self.free_cols()
+            .into_iter()
+            .map(|free| {
+                let mut scale = 1;
+                for (row, &col) in self.rows.iter().zip(&self.pivot_cols) {
+                    if row[free] != 0 {
+                        scale = checked_lcm(scale, row[col])?;
+                    }
+                }
+
+                let mut vector = vec![0; self.variables];
+                vector[free] = scale;
+                for (row, &col) in self.rows.iter().zip(&self.pivot_cols) {
+                    vector[col] = (scale / row[col])
+                        .checked_mul(-row[free])
+                        .ok_or(ArithmeticOverflow)?;
+                }
+                normalise(&mut vector);
+                Ok(vector)
+            })
+            .collect()
+    }
+}
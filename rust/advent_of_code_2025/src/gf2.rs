@@ -0,0 +1,330 @@
+use crate::jobs::{Deadline, Expired};
+use std::collections::HashMap;
+
+/// A fixed-length vector over GF(2), where addition is XOR, packed 64 bits to a word.
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::gf2::BitVector;
+/// let mut a = BitVector::from_indices(70, [0, 3, 65]);
+/// let b = BitVector::from_indices(70, [3, 4]);
+/// a.xor_assign(&b);
+/// assert_eq!(a.ones().collect::<Vec<_>>(), vec![0, 4, 65]);
+/// assert_eq!(a.count_ones(), 3);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BitVector {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVector {
+    /// All zeros.
+    pub fn new(len: usize) -> Self {
+        BitVector {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    /// Ones at `indices`, zeros elsewhere. Repeated indices cancel out.
+    pub fn from_indices(len: usize, indices: impl IntoIterator<Item = usize>) -> Self {
+        let mut vector = BitVector::new(len);
+        for index in indices {
+            vector.toggle(index);
+        }
+        vector
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.len, "Bit {} out of range for length {}", index, self.len);
+        self.words[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        assert!(index < self.len, "Bit {} out of range for length {}", index, self.len);
+        self.words[index / 64] ^= 1 << (index % 64);
+    }
+
+    /// Adds `other` in place.
+    pub fn xor_assign(&mut self, other: &BitVector) {
+        assert_eq!(self.len, other.len, "Expected vectors of the same length");
+        for (word, other_word) in self.words.iter_mut().zip(&other.words) {
+            *word ^= other_word;
+        }
+    }
+
+    /// Hamming weight.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Indices of the set bits, ascending.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(|&index| self.get(index))
+    }
+}
+
+/// A GF(2) system `A x = b` in reduced row echelon form.
+///
+/// Rows are bit vectors over the variables plus one final bit for the right-hand side.
+/// Every pivot is 1 and is the only 1 in its column.
+#[derive(Clone, Debug)]
+pub struct Rref {
+    rows: Vec<BitVector>,
+    variables: usize,
+    pivot_cols: Vec<usize>,
+}
+
+impl Rref {
+    /// Eliminates `A x = b` with `A` given as its columns.
+    ///
+    /// # Panics
+    /// If any column's length differs from `target`'s.
+    pub fn new(columns: &[BitVector], target: &BitVector) -> Self {
+        assert!(
+            columns.iter().all(|column| column.len() == target.len()),
+            "Expected every column to have {} rows",
+            target.len()
+        );
+        let variables = columns.len();
+        let mut rows: Vec<BitVector> = (0..target.len())
+            .map(|row| {
+                let mut bits = BitVector::new(variables + 1);
+                for (col, column) in columns.iter().enumerate() {
+                    if column.get(row) {
+                        bits.toggle(col);
+                    }
+                }
+                if target.get(row) {
+                    bits.toggle(variables);
+                }
+                bits
+            })
+            .collect();
+
+        let mut pivot_cols = Vec::new();
+        for col in 0..variables {
+            let pivot_row = pivot_cols.len();
+            let Some(found) = (pivot_row..rows.len()).find(|&row| rows[row].get(col)) else {
+                continue;
+            };
+            rows.swap(pivot_row, found);
+            pivot_cols.push(col);
+
+            let pivot = rows[pivot_row].clone();
+            for (index, row) in rows.iter_mut().enumerate() {
+                if index != pivot_row && row.get(col) {
+                    row.xor_assign(&pivot);
+                }
+            }
+        }
+
+        Rref {
+            rows,
+            variables,
+            pivot_cols,
+        }
+    }
+
+    /// Column of each pivot row's pivot, ascending.
+    pub fn pivot_cols(&self) -> &[usize] {
+        &self.pivot_cols
+    }
+
+    /// Columns without a pivot: variables that can be chosen freely.
+    pub fn free_cols(&self) -> Vec<usize> {
+        (0..self.variables)
+            .filter(|col| !self.pivot_cols.contains(col))
+            .collect()
+    }
+
+    /// Returns `false` if some row reduced to `0 = 1`.
+    pub fn is_consistent(&self) -> bool {
+        self.rows[self.pivot_cols.len()..]
+            .iter()
+            .all(|row| !row.get(self.variables))
+    }
+
+    /// The solution with every free variable 0, if the system is consistent.
+    pub fn particular_solution(&self) -> Option<BitVector> {
+        if !self.is_consistent() {
+            return None;
+        }
+        let mut solution = BitVector::new(self.variables);
+        for (row, &col) in self.rows.iter().zip(&self.pivot_cols) {
+            if row.get(self.variables) {
+                solution.toggle(col);
+            }
+        }
+        Some(solution)
+    }
+
+    /// A basis of the null space of `A`, one vector per free column: that free variable
+    /// set, the other free variables clear, and the pivots solved for.
+    pub fn null_space(&self) -> Vec<BitVector> {
+        self.free_cols()
+            .into_iter()
+            .map(|free| {
+                let mut vector = BitVector::new(self.variables);
+                vector.toggle(free);
+                for (row, &col) in self.rows.iter().zip(&self.pivot_cols) {
+                    if row.get(free) {
+                        vector.toggle(col);
+                    }
+                }
+                vector
+            })
+            .collect()
+    }
+}
+
+/// Above this nullity, and when splitting the variables in half is cheaper, the search
+/// switches from walking the null space to meeting in the middle.
+const EXHAUSTIVE_NULLITY: usize = 16;
+
+/// Candidates tried between checks of the deadline.
+const DEADLINE_CHECK_INTERVAL: u64 = 1 << 14;
+
+/// The solution of `A x = b` over GF(2) with the fewest ones, `A` given as its columns.
+///
+/// Every solution is the particular solution plus some combination of null-space basis
+/// vectors, so when the nullity `k` is small all `2^k` of them are walked in Gray-code
+/// order, one XOR per step. Otherwise subsets of each half of the columns are enumerated
+/// separately and matched up on the partial sums that add to `b`, which costs about
+/// `2^(n/2)` for `n` columns however large the null space is.
+///
+/// Ties go to whichever minimum is found first. Returns `None` if there is no solution,
+/// or `Err(Expired)` if `deadline` passes first.
+///
+/// # Panics
+/// If the search would have to walk `2^64` or more candidates.
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::gf2::{min_weight_solution, BitVector};
+/// use advent_of_code_2025::jobs::Deadline;
+/// // Toggle lights 1 and 2 of four, with buttons wired to {3}, {1, 3}, {2}, {2, 3},
+/// // {0, 2} and {0, 1}.
+/// let buttons = [vec![3], vec![1, 3], vec![2], vec![2, 3], vec![0, 2], vec![0, 1]];
+/// let columns: Vec<BitVector> = buttons
+///     .iter()
+///     .map(|wiring| BitVector::from_indices(4, wiring.iter().copied()))
+///     .collect();
+/// let target = BitVector::from_indices(4, [1, 2]);
+/// let best = min_weight_solution(&columns, &target, &Deadline::never()).unwrap();
+/// assert_eq!(best.unwrap().count_ones(), 2);
+///
+/// let unreachable = BitVector::from_indices(4, [0]);
+/// assert_eq!(min_weight_solution(&columns[..1], &unreachable, &Deadline::never()), Ok(None));
+/// ```
+pub fn min_weight_solution(
+    columns: &[BitVector],
+    target: &BitVector,
+    deadline: &Deadline,
+) -> Result<Option<BitVector>, Expired> {
+    let rref = Rref::new(columns, target);
+    let Some(particular) = rref.particular_solution() else {
+        return Ok(None);
+    };
+    let basis = rref.null_space();
+
+    if basis.len() <= EXHAUSTIVE_NULLITY || basis.len() <= columns.len().div_ceil(2) {
+        walk_null_space(particular, &basis, deadline).map(Some)
+    } else {
+        meet_in_the_middle(columns, target, deadline)
+    }
+}
+
+/// Lightest of `particular` plus every combination of `basis`.
+fn walk_null_space(
+    particular: BitVector,
+    basis: &[BitVector],
+    deadline: &Deadline,
+) -> Result<BitVector, Expired> {
+    assert!(basis.len() < 64, "Null space too large to walk");
+    let mut current = particular;
+    let mut best = current.clone();
+    for step in 1u64..1 << basis.len() {
+        if step.is_multiple_of(DEADLINE_CHECK_INTERVAL) {
+            deadline.check()?;
+        }
+        // Consecutive Gray codes differ in the bit at the step's trailing zero count.
+        current.xor_assign(&basis[step.trailing_zeros() as usize]);
+        if current.count_ones() < best.count_ones() {
+            best = current.clone();
+        }
+    }
+    Ok(best)
+}
+
+/// Every subset of `columns` as `(sum, chosen)`, in Gray-code order.
+fn subset_sums(columns: &[BitVector], rows: usize) -> impl Iterator<Item = (BitVector, u64)> + '_ {
+    assert!(columns.len() < 64, "Too many columns to meet in the middle");
+    let mut sum = BitVector::new(rows);
+    let mut chosen = 0u64;
+    (0u64..1 << columns.len()).map(move |step| {
+        if step > 0 {
+            let flip = step.trailing_zeros() as usize;
+            sum.xor_assign(&columns[flip]);
+            chosen ^= 1 << flip;
+        }
+        (sum.clone(), chosen)
+    })
+}
+
+fn meet_in_the_middle(
+    columns: &[BitVector],
+    target: &BitVector,
+    deadline: &Deadline,
+) -> Result<Option<BitVector>, Expired> {
+    let (left, right) = columns.split_at(columns.len() / 2);
+
+    // Lightest way to reach each sum using only the left half.
+    let mut lightest: HashMap<BitVector, u64> = HashMap::new();
+    for (step, (sum, chosen)) in (0u64..).zip(subset_sums(left, target.len())) {
+        if step.is_multiple_of(DEADLINE_CHECK_INTERVAL) {
+            deadline.check()?;
+        }
+        lightest
+            .entry(sum)
+            .and_modify(|best| {
+                if chosen.count_ones() < best.count_ones() {
+                    *best = chosen;
+                }
+            })
+            .or_insert(chosen);
+    }
+
+    let mut best: Option<(u64, u64)> = None;
+    for (step, (mut sum, right_chosen)) in (0u64..).zip(subset_sums(right, target.len())) {
+        if step.is_multiple_of(DEADLINE_CHECK_INTERVAL) {
+            deadline.check()?;
+        }
+        // The left half has to make up the difference.
+        sum.xor_assign(target);
+        if let Some(&left_chosen) = lightest.get(&sum) {
+            let weight = left_chosen.count_ones() + right_chosen.count_ones();
+            if best.is_none_or(|(l, r)| weight < l.count_ones() + r.count_ones()) {
+                best = Some((left_chosen, right_chosen));
+            }
+        }
+    }
+
+    let Some((left_chosen, right_chosen)) = best else {
+        return Ok(None);
+    };
+    let left_indices = (0..left.len()).filter(|&i| left_chosen >> i & 1 == 1);
+    let right_indices = (0..right.len())
+        .filter(|&i| right_chosen >> i & 1 == 1)
+        .map(|i| left.len() + i);
+    Ok(Some(BitVector::from_indices(columns.len(), left_indices.chain(right_indices))))
+}
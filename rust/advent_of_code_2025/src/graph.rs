@@ -0,0 +1,268 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+/// Dense ID of a node in a [`Graph`], in the order names were first seen.
+pub type NodeId = u32;
+
+/// Returned when a graph that should be acyclic isn't, naming the nodes on one cycle in
+/// edge order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    pub names: Vec<String>,
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Found a cycle: {}", self.names.join(" -> "))?;
+        if let Some(first) = self.names.first() {
+            write!(f, " -> {}", first)?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects named edges for a [`Graph`], interning each name the first time it's seen.
+#[derive(Clone, Debug, Default)]
+pub struct GraphBuilder {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    edges: Vec<(NodeId, NodeId)>,
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        GraphBuilder::default()
+    }
+
+    /// The ID for `name`, adding it as a node if it's new.
+    ///
+    /// # Panics
+    /// If there are already `u32::MAX` nodes.
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = NodeId::try_from(self.names.len()).expect("Should have fewer than 2^32 nodes");
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    /// Adds a directed edge, adding either endpoint as a node if it's new.
+    pub fn edge(&mut self, from: &str, to: &str) {
+        let (from, to) = (self.node(from), self.node(to));
+        self.edges.push((from, to));
+    }
+
+    /// Packs the edges into compressed sparse rows. Each node's successors keep the order
+    /// their edges were added in.
+    pub fn build(self) -> Graph {
+        let mut offsets = vec![0; self.names.len() + 1];
+        for &(from, _) in &self.edges {
+            offsets[from as usize + 1] += 1;
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+
+        let mut next = offsets.clone();
+        let mut targets = vec![0; self.edges.len()];
+        for &(from, to) in &self.edges {
+            targets[next[from as usize]] = to;
+            next[from as usize] += 1;
+        }
+
+        Graph {
+            names: self.names,
+            ids: self.ids,
+            offsets,
+            targets,
+        }
+    }
+}
+
+/// A directed graph over interned node names, with adjacency in compressed sparse row
+/// form: node `n`'s successors are `targets[offsets[n]..offsets[n + 1]]`.
+///
+/// Nodes are numbered `0..len()`, so per-node state can live in a `Vec` indexed by
+/// [`NodeId`] rather than a map keyed by name.
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::graph::GraphBuilder;
+/// let mut builder = GraphBuilder::new();
+/// builder.edge("you", "bbb");
+/// builder.edge("you", "ccc");
+/// builder.edge("bbb", "out");
+/// let graph = builder.build();
+///
+/// let you = graph.id("you").unwrap();
+/// let names: Vec<&str> = graph.successors(you).iter().map(|&n| graph.name(n)).collect();
+/// assert_eq!(names, vec!["bbb", "ccc"]);
+/// assert_eq!(graph.len(), 4);
+/// assert_eq!(graph.edge_count(), 3);
+/// assert!(graph.successors(graph.id("out").unwrap()).is_empty());
+/// assert_eq!(graph.id("zzz"), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    offsets: Vec<usize>,
+    targets: Vec<NodeId>,
+}
+
+impl Graph {
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// The ID of `name`, if it's a node.
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, node: NodeId) -> &str {
+        &self.names[node as usize]
+    }
+
+    /// IDs `0..len()`.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        0..self.names.len() as NodeId
+    }
+
+    pub fn successors(&self, node: NodeId) -> &[NodeId] {
+        let node = node as usize;
+        &self.targets[self.offsets[node]..self.offsets[node + 1]]
+    }
+
+    /// The subgraph induced by the nodes `keep` accepts: those nodes, renumbered in their
+    /// original order, and every edge between two of them.
+    ///
+    /// # Example
+    /// ```
+    /// use advent_of_code_2025::graph::GraphBuilder;
+    /// let mut builder = GraphBuilder::new();
+    /// builder.edge("a", "b");
+    /// builder.edge("b", "c");
+    /// builder.edge("a", "c");
+    /// let graph = builder.build();
+    /// let b = graph.id("b").unwrap();
+    ///
+    /// let without_b = graph.subgraph(|node| node != b);
+    /// assert_eq!(without_b.len(), 2);
+    /// assert_eq!(without_b.edge_count(), 1);
+    /// assert_eq!(without_b.id("c"), Some(1));
+    /// ```
+    pub fn subgraph(&self, keep: impl Fn(NodeId) -> bool) -> Graph {
+        let mut builder = GraphBuilder::new();
+        for node in self.nodes().filter(|&n| keep(n)) {
+            builder.node(self.name(node));
+        }
+        for from in self.nodes().filter(|&n| keep(n)) {
+            for &to in self.successors(from).iter().filter(|&&n| keep(n)) {
+                builder.edge(self.name(from), self.name(to));
+            }
+        }
+        builder.build()
+    }
+
+    /// Every node ordered so that each edge points forwards, found with Kahn's algorithm.
+    ///
+    /// # Example
+    /// ```
+    /// use advent_of_code_2025::graph::GraphBuilder;
+    /// let mut builder = GraphBuilder::new();
+    /// builder.edge("b", "c");
+    /// builder.edge("a", "b");
+    /// let graph = builder.build();
+    /// let order: Vec<&str> = graph
+    ///     .topological_order()
+    ///     .unwrap()
+    ///     .into_iter()
+    ///     .map(|n| graph.name(n))
+    ///     .collect();
+    /// assert_eq!(order, vec!["a", "b", "c"]);
+    ///
+    /// let mut builder = GraphBuilder::new();
+    /// builder.edge("a", "b");
+    /// builder.edge("b", "c");
+    /// builder.edge("c", "b");
+    /// let cycle = builder.build().topological_order().unwrap_err();
+    /// assert_eq!(cycle.to_string(), "Found a cycle: b -> c -> b");
+    /// ```
+    pub fn topological_order(&self) -> Result<Vec<NodeId>, Cycle> {
+        let mut in_degree = vec![0usize; self.len()];
+        for &to in &self.targets {
+            in_degree[to as usize] += 1;
+        }
+
+        let mut ready: VecDeque<NodeId> =
+            self.nodes().filter(|&n| in_degree[n as usize] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+            for &next in self.successors(node) {
+                in_degree[next as usize] -= 1;
+                if in_degree[next as usize] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            Ok(order)
+        } else {
+            Err(self.find_cycle(&in_degree))
+        }
+    }
+
+    /// Digs a cycle out of the nodes Kahn's algorithm couldn't order.
+    ///
+    /// Each of those still has an edge in from another of them, so following those edges
+    /// backwards from any one of them must eventually come back round.
+    fn find_cycle(&self, in_degree: &[usize]) -> Cycle {
+        let stuck = |node: NodeId| in_degree[node as usize] > 0;
+        let mut predecessor = vec![None; self.len()];
+        for from in self.nodes().filter(|&n| stuck(n)) {
+            for &to in self.successors(from) {
+                if stuck(to) {
+                    predecessor[to as usize] = Some(from);
+                }
+            }
+        }
+
+        let start = self.nodes().find(|&n| stuck(n)).expect("Should have an unordered node");
+        let mut seen = vec![false; self.len()];
+        let mut node = start;
+        while !seen[node as usize] {
+            seen[node as usize] = true;
+            node = predecessor[node as usize].expect("Should have a stuck predecessor");
+        }
+
+        // `node` is on the cycle; walk it once more, backwards, then flip it round.
+        let mut cycle = vec![node];
+        let mut previous = predecessor[node as usize].expect("Should have a stuck predecessor");
+        while previous != node {
+            cycle.push(previous);
+            previous = predecessor[previous as usize].expect("Should have a stuck predecessor");
+        }
+        cycle.reverse();
+
+        // Start from the earliest node so the same cycle is always reported the same way.
+        let earliest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+        cycle.rotate_left(earliest);
+        Cycle {
+            names: cycle.iter().map(|&n| self.name(n).to_string()).collect(),
+        }
+    }
+}
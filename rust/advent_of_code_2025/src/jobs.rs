@@ -0,0 +1,251 @@
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// When a job should give up. Jobs can't be stopped from outside, so long-running ones
+/// are expected to check [`Deadline::check`] now and then and return early.
+#[derive(Clone, Copy, Debug)]
+pub struct Deadline {
+    at: Option<Instant>,
+}
+
+impl Deadline {
+    /// A deadline that never expires.
+    pub fn never() -> Self {
+        Deadline { at: None }
+    }
+
+    pub fn expired(&self) -> bool {
+        self.at.is_some_and(|at| Instant::now() >= at)
+    }
+
+    /// `Err(Expired)` once the deadline has passed, for use with `?`.
+    pub fn check(&self) -> Result<(), Expired> {
+        if self.expired() { Err(Expired) } else { Ok(()) }
+    }
+}
+
+/// Returned by a job that gave up because its [`Deadline`] passed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Expired;
+
+impl fmt::Display for Expired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Gave up at the deadline")
+    }
+}
+
+/// What became of one item.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome<T> {
+    Done(T),
+    /// The job gave up at its deadline.
+    TimedOut,
+}
+
+/// One item's outcome and how long its job ran.
+#[derive(Clone, Debug)]
+pub struct JobReport<T> {
+    pub elapsed: Duration,
+    pub outcome: Outcome<T>,
+}
+
+/// Runs a job over independent items in parallel.
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::jobs::{Expired, JobRunner, Outcome};
+/// use std::time::Duration;
+/// let runner = JobRunner {
+///     threads: 2,
+///     timeout: Some(Duration::from_millis(50)),
+///     ..JobRunner::default()
+/// };
+/// let reports = runner.run(&[1u64, 2, 1000], |&n, deadline| {
+///     // Pretend big items are slow, giving up once past the deadline.
+///     while n > 100 {
+///         deadline.check()?;
+///         std::thread::sleep(Duration::from_millis(5));
+///     }
+///     Ok::<_, Expired>(n * 2)
+/// });
+/// assert_eq!(reports[0].outcome, Outcome::Done(2));
+/// assert_eq!(reports[1].outcome, Outcome::Done(4));
+/// assert_eq!(reports[2].outcome, Outcome::TimedOut);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct JobRunner {
+    /// Worker threads; 0 lets the thread pool pick one per core.
+    pub threads: usize,
+    /// How long each item may take before its deadline passes.
+    pub timeout: Option<Duration>,
+    /// If set, a progress line with this label is kept up to date on stderr, naming the
+    /// longest-running item.
+    pub progress: Option<String>,
+}
+
+/// Sets its flag when dropped, including while unwinding from a panic.
+struct SetOnDrop<'a>(&'a AtomicBool);
+
+impl Drop for SetOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// How often the progress line is redrawn.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+impl JobRunner {
+    /// Runs `job` on every item, returning one report per item in the same order.
+    ///
+    /// Each job is handed a [`Deadline`] `timeout` after it starts. Jobs can't be stopped
+    /// from outside, so it's up to the job to check it and return [`Expired`], which is
+    /// recorded as timed out. A job that finishes late anyway keeps its result.
+    pub fn run<I, T, F>(&self, items: &[I], job: F) -> Vec<JobReport<T>>
+    where
+        I: Sync,
+        T: Send,
+        F: Fn(&I, &Deadline) -> Result<T, Expired> + Sync,
+    {
+        self.run_numbered(items, |i| i, job)
+    }
+
+    /// [`JobRunner::run`], with the progress line calling `items[i]` item `number(i) + 1`.
+    fn run_numbered<I, T, N, F>(&self, items: &[I], number: N, job: F) -> Vec<JobReport<T>>
+    where
+        I: Sync,
+        T: Send,
+        N: Fn(usize) -> usize + Sync,
+        F: Fn(&I, &Deadline) -> Result<T, Expired> + Sync,
+    {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.threads)
+            .build()
+            .expect("Should be able to build a thread pool");
+
+        // Start times of the items currently running, for the progress line.
+        let running: Mutex<Vec<Option<Instant>>> = Mutex::new(vec![None; items.len()]);
+        let finished = AtomicUsize::new(0);
+        let timed_out = AtomicUsize::new(0);
+        // Set once the jobs are over, whether they all finished or one panicked, so the
+        // progress thread stops and the scope can pass the panic on.
+        let stopped = AtomicBool::new(false);
+
+        thread::scope(|scope| {
+            if let Some(label) = &self.progress {
+                let (running, finished, timed_out, stopped, number) =
+                    (&running, &finished, &timed_out, &stopped, &number);
+                scope.spawn(move || {
+                    loop {
+                        let over = stopped.load(Ordering::SeqCst);
+                        let done = finished.load(Ordering::SeqCst);
+                        let slowest = running
+                            .lock()
+                            .unwrap()
+                            .iter()
+                            .enumerate()
+                            .filter_map(|(i, start)| start.map(|start| (start.elapsed(), i)))
+                            .max();
+                        let mut line = format!(
+                            "{}: {}/{} done, {} timed out",
+                            label,
+                            done,
+                            items.len(),
+                            timed_out.load(Ordering::SeqCst)
+                        );
+                        if let Some((elapsed, i)) = slowest {
+                            let item = number(i) + 1;
+                            line.push_str(&format!(", item {} running for {:.1?}", item, elapsed));
+                        }
+                        // Pad so a shorter line fully covers the previous one.
+                        eprint!("\r{:<80}", line);
+                        if over {
+                            eprintln!();
+                            break;
+                        }
+                        thread::sleep(PROGRESS_INTERVAL);
+                    }
+                });
+            }
+
+            let _stop_progress = SetOnDrop(&stopped);
+            pool.install(|| {
+                items
+                    .par_iter()
+                    .enumerate()
+                    .map(|(i, item)| {
+                        let start = Instant::now();
+                        running.lock().unwrap()[i] = Some(start);
+                        let deadline = Deadline {
+                            at: self.timeout.map(|timeout| start + timeout),
+                        };
+
+                        let result = job(item, &deadline);
+
+                        let elapsed = start.elapsed();
+                        let outcome = match result {
+                            Ok(value) => Outcome::Done(value),
+                            Err(Expired) => {
+                                timed_out.fetch_add(1, Ordering::SeqCst);
+                                Outcome::TimedOut
+                            }
+                        };
+                        running.lock().unwrap()[i] = None;
+                        finished.fetch_add(1, Ordering::SeqCst);
+                        JobReport { elapsed, outcome }
+                    })
+                    .collect()
+            })
+        })
+    }
+
+    /// Like [`JobRunner::run`], but runs each distinct item only once and hands every
+    /// repeat of it the same report. Progress counts distinct items, each numbered by
+    /// where it first appears in `items`.
+    ///
+    /// # Example
+    /// ```
+    /// use advent_of_code_2025::jobs::{Expired, JobRunner, Outcome};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// let calls = AtomicUsize::new(0);
+    /// let reports = JobRunner::default().run_cached(&["a", "b", "a"], |item, _| {
+    ///     calls.fetch_add(1, Ordering::SeqCst);
+    ///     Ok::<_, Expired>(item.to_uppercase())
+    /// });
+    /// assert_eq!(calls.load(Ordering::SeqCst), 2);
+    /// assert_eq!(reports[2].outcome, Outcome::Done("A".to_string()));
+    /// ```
+    pub fn run_cached<I, T, F>(&self, items: &[I], job: F) -> Vec<JobReport<T>>
+    where
+        I: Eq + Hash + Sync,
+        T: Clone + Send,
+        F: Fn(&I, &Deadline) -> Result<T, Expired> + Sync,
+    {
+        // Each distinct item, with the index it first appears at.
+        let mut distinct: Vec<(usize, &I)> = Vec::new();
+        let mut first_seen: HashMap<&I, usize> = HashMap::new();
+        let slots: Vec<usize> = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                *first_seen.entry(item).or_insert_with(|| {
+                    distinct.push((index, item));
+                    distinct.len() - 1
+                })
+            })
+            .collect();
+
+        let reports = self.run_numbered(
+            &distinct,
+            |i| distinct[i].0,
+            |&(_, item), deadline| job(item, deadline),
+        );
+        slots.iter().map(|&slot| reports[slot].clone()).collect()
+    }
+}
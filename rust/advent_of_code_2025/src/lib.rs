@@ -1,3 +1,16 @@
+pub mod counting;
+pub mod disjoint_set;
+pub mod elimination;
+pub mod gf2;
+pub mod graph;
+pub mod jobs;
+pub mod paths;
+pub mod rectilinear;
+pub mod spatial;
+
+pub use disjoint_set::DisjointSet;
+pub use rectilinear::RectilinearPolygon;
+
 /// Checks if the given coordinates are within the grid bounds.
 ///
 /// # Arguments
@@ -23,6 +36,31 @@ pub fn in_bounds(grid: &[Vec<char>], row: i32, col: i32) -> bool {
     row >= 0 && row < rows && col >= 0 && col < cols
 }
 
+/// Finds the value given for a command-line option, as in `--name value`.
+///
+/// # Arguments
+/// * `args` - The command-line arguments
+/// * `name` - The option, including its leading dashes
+///
+/// # Returns
+/// The argument after the first `name`, or `None` if `name` isn't given
+///
+/// # Panics
+/// If `name` is the last argument, so has no value
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::option_value;
+/// let args: Vec<String> = ["day08", "--metric", "manhattan"].map(String::from).to_vec();
+/// assert_eq!(option_value(&args, "--metric"), Some("manhattan".to_string()));
+/// assert_eq!(option_value(&args, "--connections"), None);
+/// ```
+pub fn option_value(args: &[String], name: &str) -> Option<String> {
+    args.iter()
+        .position(|arg| arg == name)
+        .map(|i| args.get(i + 1).cloned().unwrap_or_else(|| panic!("{} needs a value", name)))
+}
+
 /// Counts neighbouring cells matching any of the target characters.
 ///
 /// # Arguments
@@ -72,10 +110,10 @@ pub fn count_neighbors(
             let neighbour_row = row as i32 + row_offset;
             let neighbour_col = col as i32 + col_offset;
 
-            if in_bounds(grid, neighbour_row, neighbour_col) {
-                if target_chars.contains(&grid[neighbour_row as usize][neighbour_col as usize]) {
-                    count += 1;
-                }
+            if in_bounds(grid, neighbour_row, neighbour_col)
+                && target_chars.contains(&grid[neighbour_row as usize][neighbour_col as usize])
+            {
+                count += 1;
             }
         }
     }
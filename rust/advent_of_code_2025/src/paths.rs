@@ -0,0 +1,211 @@
+use crate::counting::{CountOverflow, Counting};
+use crate::graph::{Cycle, Graph, NodeId};
+
+/// Which paths to count: those from `source` to `sink` that visit every `required` node,
+/// none of the `avoided` nodes, and, if `ordered`, the required nodes in the order given.
+///
+/// Paths stop at the sink, so they never pass through it on the way somewhere else.
+#[derive(Clone, Debug)]
+pub struct PathQuery {
+    pub source: NodeId,
+    pub sink: NodeId,
+    pub required: Vec<NodeId>,
+    pub avoided: Vec<NodeId>,
+    pub ordered: bool,
+}
+
+impl PathQuery {
+    /// Every path from `source` to `sink`.
+    pub fn new(source: NodeId, sink: NodeId) -> Self {
+        PathQuery {
+            source,
+            sink,
+            required: Vec::new(),
+            avoided: Vec::new(),
+            ordered: false,
+        }
+    }
+}
+
+/// Adds `count` paths to `total`, where `None` means none so far.
+fn add_paths<C: Counting>(
+    counting: &C,
+    total: &mut Option<C::Value>,
+    count: &C::Value,
+) -> Result<(), CountOverflow> {
+    *total = Some(match total {
+        Some(total) => counting.add(total, count)?,
+        None => count.clone(),
+    });
+    Ok(())
+}
+
+/// Counts paths through a directed acyclic [`Graph`].
+///
+/// The topological order is worked out once, up front, and then each query is a single
+/// forward pass over it. Alongside each node the pass tracks which of the required nodes
+/// have been visited so far: as a bitmask, or just how many when they must come in order.
+///
+/// Path counts can grow exponentially with the graph, so they're accumulated with a
+/// [`Counting`] strategy that reports overflow instead of wrapping.
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::counting::{CheckedU128, Modulo};
+/// use advent_of_code_2025::graph::GraphBuilder;
+/// use advent_of_code_2025::paths::{PathCounter, PathQuery};
+/// // Two ways from a to d, via b or via c, then on to e.
+/// let mut builder = GraphBuilder::new();
+/// for (from, to) in [("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("b", "c"), ("d", "e")] {
+///     builder.edge(from, to);
+/// }
+/// let graph = builder.build();
+/// let id = |name| graph.id(name).unwrap();
+/// let counter = PathCounter::new(&graph).unwrap();
+///
+/// let mut query = PathQuery::new(id("a"), id("e"));
+/// assert_eq!(counter.count(&query, &CheckedU128), Ok(3));
+/// assert_eq!(counter.count(&query, &Modulo::new(2)), Ok(1));
+///
+/// query.required = vec![id("c"), id("b")];
+/// assert_eq!(counter.count(&query, &CheckedU128), Ok(1));
+/// query.ordered = true;
+/// assert_eq!(counter.count(&query, &CheckedU128), Ok(0));
+///
+/// query.required.clear();
+/// query.avoided = vec![id("c")];
+/// assert_eq!(counter.count(&query, &CheckedU128), Ok(1));
+///
+/// let from_a = counter.paths_from(id("a"), &CheckedU128).unwrap();
+/// assert_eq!(from_a[id("d") as usize], Some(3));
+/// let to_d = counter.paths_to(id("d"), &CheckedU128).unwrap();
+/// assert_eq!(to_d[id("b") as usize], Some(2));
+/// assert_eq!(to_d[id("e") as usize], None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PathCounter<'a> {
+    graph: &'a Graph,
+    order: Vec<NodeId>,
+}
+
+impl<'a> PathCounter<'a> {
+    /// Fails if the graph has a cycle, since then there could be infinitely many paths.
+    pub fn new(graph: &'a Graph) -> Result<Self, Cycle> {
+        Ok(PathCounter {
+            graph,
+            order: graph.topological_order()?,
+        })
+    }
+
+    /// Number of paths from `source` to each node, or `None` for nodes it can't reach.
+    ///
+    /// `None` rather than zero, since a count modulo something can be zero too.
+    pub fn paths_from<C: Counting>(
+        &self,
+        source: NodeId,
+        counting: &C,
+    ) -> Result<Vec<Option<C::Value>>, CountOverflow> {
+        let mut paths: Vec<Option<C::Value>> = vec![None; self.graph.len()];
+        paths[source as usize] = Some(counting.one());
+        for &node in &self.order {
+            let Some(count) = paths[node as usize].clone() else {
+                continue;
+            };
+            for &next in self.graph.successors(node) {
+                add_paths(counting, &mut paths[next as usize], &count)?;
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Number of paths from each node to `sink`, or `None` for nodes that can't reach it.
+    /// As with [`PathQuery`], paths stop at the sink.
+    pub fn paths_to<C: Counting>(
+        &self,
+        sink: NodeId,
+        counting: &C,
+    ) -> Result<Vec<Option<C::Value>>, CountOverflow> {
+        let mut paths: Vec<Option<C::Value>> = vec![None; self.graph.len()];
+        paths[sink as usize] = Some(counting.one());
+        for &node in self.order.iter().rev().filter(|&&n| n != sink) {
+            let mut total = None;
+            for &next in self.graph.successors(node) {
+                if let Some(count) = &paths[next as usize] {
+                    add_paths(counting, &mut total, count)?;
+                }
+            }
+            paths[node as usize] = total;
+        }
+        Ok(paths)
+    }
+
+    /// Number of paths matching `query`, counted with `counting`.
+    ///
+    /// # Panics
+    /// If a node is required twice, or more than 63 nodes are required without an order.
+    pub fn count<C: Counting>(
+        &self,
+        query: &PathQuery,
+        counting: &C,
+    ) -> Result<C::Value, CountOverflow> {
+        let nodes = self.graph.len();
+        // Which required node each node is, if any.
+        let mut required_index = vec![None; nodes];
+        for (index, &node) in query.required.iter().enumerate() {
+            assert!(
+                required_index[node as usize].replace(index).is_none(),
+                "Node {} is required more than once",
+                self.graph.name(node)
+            );
+        }
+        let mut avoided = vec![false; nodes];
+        for &node in &query.avoided {
+            avoided[node as usize] = true;
+        }
+
+        let (states, done) = if query.ordered {
+            (query.required.len() + 1, query.required.len())
+        } else {
+            assert!(query.required.len() < 64, "Too many required nodes for a bitmask");
+            (1 << query.required.len(), (1 << query.required.len()) - 1)
+        };
+        // The state after stepping onto `node` from `state`, or `None` if the path is
+        // ruled out there.
+        let visit = |node: NodeId, state: usize| {
+            if avoided[node as usize] {
+                return None;
+            }
+            match required_index[node as usize] {
+                None => Some(state),
+                Some(index) if query.ordered => (index == state).then_some(state + 1),
+                Some(index) => Some(state | 1 << index),
+            }
+        };
+
+        // `None` where no path has got to yet, so those can be skipped.
+        let mut paths: Vec<Option<C::Value>> = vec![None; nodes * states];
+        let Some(start) = visit(query.source, 0) else {
+            return Ok(counting.zero());
+        };
+        paths[query.source as usize * states + start] = Some(counting.one());
+        for &node in &self.order {
+            if node == query.sink {
+                continue;
+            }
+            for state in 0..states {
+                let Some(count) = paths[node as usize * states + state].take() else {
+                    continue;
+                };
+                for &next in self.graph.successors(node) {
+                    if let Some(next_state) = visit(next, state) {
+                        let total = &mut paths[next as usize * states + next_state];
+                        add_paths(counting, total, &count)?;
+                    }
+                }
+            }
+        }
+        Ok(paths[query.sink as usize * states + done]
+            .take()
+            .unwrap_or_else(|| counting.zero()))
+    }
+}
@@ -0,0 +1,499 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// Which way round a polygon's vertices go, as drawn with y increasing
+/// downwards (the puzzle's convention).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// What [`validate`] learned about a well-formed polygon.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PolygonSummary {
+    pub orientation: Orientation,
+    /// Area enclosed by the loop through the vertex centres (shoelace formula).
+    pub area: i128,
+    /// Tiles on or inside the boundary.
+    pub tiles: i128,
+}
+
+/// Why a list of vertices is not a simple, closed, axis-aligned loop.
+/// Vertices are identified by their index in the list and their coordinates.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PolygonError {
+    TooFewVertices(usize),
+    DuplicateVertex {
+        first: usize,
+        second: usize,
+        vertex: (i64, i64),
+    },
+    DiagonalEdge {
+        from: usize,
+        to: usize,
+        from_vertex: (i64, i64),
+        to_vertex: (i64, i64),
+    },
+    /// The edge leaving vertex `at` doubles back over the edge arriving at it.
+    FoldsBack { at: usize, vertex: (i64, i64) },
+    /// The edges starting at vertices `first` and `second` touch or cross.
+    SelfIntersection {
+        first: usize,
+        second: usize,
+        first_vertex: (i64, i64),
+        second_vertex: (i64, i64),
+    },
+}
+
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolygonError::TooFewVertices(count) => {
+                write!(f, "A rectilinear polygon needs at least 4 vertices, got {}", count)
+            }
+            PolygonError::DuplicateVertex { first, second, vertex } => write!(
+                f,
+                "Vertex {} {:?} repeats vertex {}",
+                second, vertex, first
+            ),
+            PolygonError::DiagonalEdge { from, to, from_vertex, to_vertex } => write!(
+                f,
+                "Edge from vertex {} {:?} to vertex {} {:?} is not horizontal or vertical",
+                from, from_vertex, to, to_vertex
+            ),
+            PolygonError::FoldsBack { at, vertex } => write!(
+                f,
+                "The loop doubles back on itself at vertex {} {:?}",
+                at, vertex
+            ),
+            PolygonError::SelfIntersection { first, second, first_vertex, second_vertex } => {
+                write!(
+                    f,
+                    "Edge starting at vertex {} {:?} intersects edge starting at vertex {} {:?}",
+                    first, first_vertex, second, second_vertex
+                )
+            }
+        }
+    }
+}
+
+/// Checks that `vertices`, in order and closing back to the first, form a
+/// simple rectilinear polygon: every edge horizontal or vertical, no vertex
+/// repeated, and no two edges touching except neighbours at their shared
+/// vertex.
+///
+/// Intersections are found by comparing every pair of edges, O(n²).
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::rectilinear::{validate, Orientation, PolygonError};
+/// let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+/// let summary = validate(&square).unwrap();
+/// assert_eq!(summary.orientation, Orientation::Clockwise);
+/// assert_eq!((summary.area, summary.tiles), (16, 25));
+///
+/// let bow_tie = [(0, 0), (4, 0), (4, 4), (2, 4), (2, -2), (0, -2)];
+/// assert!(matches!(
+///     validate(&bow_tie),
+///     Err(PolygonError::SelfIntersection { first: 0, second: 3, .. })
+/// ));
+/// ```
+pub fn validate(vertices: &[(i64, i64)]) -> Result<PolygonSummary, PolygonError> {
+    let n = vertices.len();
+    if n < 4 {
+        return Err(PolygonError::TooFewVertices(n));
+    }
+
+    let mut seen: HashMap<(i64, i64), usize> = HashMap::new();
+    for (i, &vertex) in vertices.iter().enumerate() {
+        if let Some(&first) = seen.get(&vertex) {
+            return Err(PolygonError::DuplicateVertex { first, second: i, vertex });
+        }
+        seen.insert(vertex, i);
+    }
+
+    let edge = |i: usize| (vertices[i], vertices[(i + 1) % n]);
+    for i in 0..n {
+        let (from_vertex, to_vertex) = edge(i);
+        if from_vertex.0 != to_vertex.0 && from_vertex.1 != to_vertex.1 {
+            let to = (i + 1) % n;
+            return Err(PolygonError::DiagonalEdge { from: i, to, from_vertex, to_vertex });
+        }
+    }
+
+    for i in 0..n {
+        // Neighbouring edges share a vertex, so only overlap if the second
+        // reverses along the same line as the first.
+        let ((ax, ay), (bx, by)) = edge(i);
+        let (_, (cx, cy)) = edge((i + 1) % n);
+        let reversed = ((bx - ax).signum() * (cx - bx).signum() < 0)
+            || ((by - ay).signum() * (cy - by).signum() < 0);
+        if reversed {
+            let at = (i + 1) % n;
+            return Err(PolygonError::FoldsBack { at, vertex: vertices[at] });
+        }
+
+        // Axis-aligned segments are their own bounding boxes, so they
+        // intersect exactly when the boxes overlap.
+        let bounds = |((x1, y1), (x2, y2)): ((i64, i64), (i64, i64))| {
+            (x1.min(x2), x1.max(x2), y1.min(y2), y1.max(y2))
+        };
+        let (min_x, max_x, min_y, max_y) = bounds(edge(i));
+        // Skip the neighbours on either side.
+        let last = if i == 0 { n - 1 } else { n };
+        for j in i + 2..last {
+            let (other_min_x, other_max_x, other_min_y, other_max_y) = bounds(edge(j));
+            if min_x <= other_max_x
+                && other_min_x <= max_x
+                && min_y <= other_max_y
+                && other_min_y <= max_y
+            {
+                return Err(PolygonError::SelfIntersection {
+                    first: i,
+                    second: j,
+                    first_vertex: vertices[i],
+                    second_vertex: vertices[j],
+                });
+            }
+        }
+    }
+
+    let (twice_area, perimeter) = (0..n).fold((0i128, 0i128), |(area, perimeter), i| {
+        let ((x1, y1), (x2, y2)) = edge(i);
+        (
+            area + x1 as i128 * y2 as i128 - x2 as i128 * y1 as i128,
+            perimeter + (x2 - x1).abs() as i128 + (y2 - y1).abs() as i128,
+        )
+    });
+    // With y pointing down a positive shoelace sum is clockwise on screen.
+    let orientation = if twice_area > 0 {
+        Orientation::Clockwise
+    } else {
+        Orientation::CounterClockwise
+    };
+    let area = twice_area.abs() / 2;
+
+    Ok(PolygonSummary {
+        orientation,
+        area,
+        // Pick's theorem: interior lattice points plus boundary ones.
+        tiles: area + perimeter / 2 + 1,
+    })
+}
+
+/// A closed rectilinear (axis-aligned) polygon on an integer tile grid,
+/// answering "is this tile / rectangle inside?" independently of how large
+/// the coordinates are.
+///
+/// Vertices are tile centres. A tile is inside if it lies on the boundary or
+/// strictly within it.
+///
+/// Internally the plane is coordinate-compressed: every distinct vertex x
+/// gets a column, and so does each gap between consecutive distinct x values
+/// (likewise for y). Polygon edges only run along vertex coordinates, so all
+/// tiles in a compressed cell share the same inside/outside status. A 2D
+/// prefix sum of the number of *outside* tiles per cell then answers any
+/// rectangle query with four lookups.
+///
+/// # Example
+/// ```
+/// use advent_of_code_2025::RectilinearPolygon;
+/// // An L shape.
+/// let polygon = RectilinearPolygon::new(&[(0, 0), (10, 0), (10, 4), (4, 4), (4, 10), (0, 10)]);
+/// assert!(polygon.contains(2, 8));
+/// assert!(!polygon.contains(8, 8));
+/// assert!(polygon.contains_rectangle((0, 0), (10, 4)));
+/// assert!(!polygon.contains_rectangle((0, 0), (10, 10)));
+/// ```
+pub struct RectilinearPolygon {
+    vertices: Vec<(i64, i64)>,
+    // Distinct vertex coordinates, sorted.
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    cols: usize,
+    rows: usize,
+    // Compressed cells holding at least one outside tile.
+    blocked: Vec<bool>,
+    // `outside_prefix[row * (cols + 1) + col]` is the number of outside tiles
+    // in compressed rows `< row` and columns `< col`.
+    outside_prefix: Vec<i128>,
+}
+
+/// An axis-aligned rectangle of tiles given by two opposite corners.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rectangle {
+    /// Number of tiles covered, corners included.
+    pub area: i128,
+    pub corners: ((i64, i64), (i64, i64)),
+}
+
+/// Compressed index of `value`: odd indices are the vertex coordinates
+/// themselves, even indices the gaps around them (0 and the last index being
+/// everything before the first and after the last).
+fn compress(values: &[i64], value: i64) -> usize {
+    match values.binary_search(&value) {
+        Ok(i) => 2 * i + 1,
+        Err(i) => 2 * i,
+    }
+}
+
+/// Number of integer coordinates covered by compressed index `index`. The
+/// unbounded outer gaps count as one so they are never mistaken for empty.
+fn span(values: &[i64], index: usize) -> i128 {
+    if index % 2 == 1 || index == 0 || index == 2 * values.len() {
+        1
+    } else {
+        let (before, after) = (values[index / 2 - 1], values[index / 2]);
+        (after - before - 1) as i128
+    }
+}
+
+impl RectilinearPolygon {
+    /// Builds the polygon from its vertices in order; the last vertex
+    /// connects back to the first.
+    ///
+    /// # Panics
+    /// If any edge is not horizontal or vertical.
+    pub fn new(vertices: &[(i64, i64)]) -> Self {
+        let mut xs: Vec<i64> = vertices.iter().map(|&(x, _)| x).collect();
+        let mut ys: Vec<i64> = vertices.iter().map(|&(_, y)| y).collect();
+        xs.sort_unstable();
+        xs.dedup();
+        ys.sort_unstable();
+        ys.dedup();
+
+        let cols = 2 * xs.len() + 1;
+        let rows = 2 * ys.len() + 1;
+
+        // Draw the boundary in compressed space.
+        let mut boundary = vec![false; rows * cols];
+        for (i, &(x1, y1)) in vertices.iter().enumerate() {
+            let (x2, y2) = vertices[(i + 1) % vertices.len()];
+            assert!(
+                x1 == x2 || y1 == y2,
+                "Expected all edges to be straight lines, one that wasn't: {:?}",
+                ((x1, y1), (x2, y2))
+            );
+            let (c1, c2) = (compress(&xs, x1), compress(&xs, x2));
+            let (r1, r2) = (compress(&ys, y1), compress(&ys, y2));
+            for row in r1.min(r2)..=r1.max(r2) {
+                for col in c1.min(c2)..=c1.max(c2) {
+                    boundary[row * cols + col] = true;
+                }
+            }
+        }
+
+        // Flood fill the outside from the top-left corner, which is always
+        // beyond every vertex.
+        let mut outside = vec![false; rows * cols];
+        let mut stack = vec![(0usize, 0usize)];
+        outside[0] = true;
+        while let Some((row, col)) = stack.pop() {
+            let neighbours = [
+                (row.wrapping_sub(1), col),
+                (row + 1, col),
+                (row, col.wrapping_sub(1)),
+                (row, col + 1),
+            ];
+            for (next_row, next_col) in neighbours {
+                if next_row >= rows || next_col >= cols {
+                    continue;
+                }
+                let index = next_row * cols + next_col;
+                if !outside[index] && !boundary[index] {
+                    outside[index] = true;
+                    stack.push((next_row, next_col));
+                }
+            }
+        }
+
+        // Prefix sum of outside tile counts. Gaps between adjacent
+        // coordinates hold no tiles, so never block anything.
+        let mut blocked = vec![false; rows * cols];
+        let mut outside_prefix = vec![0i128; (rows + 1) * (cols + 1)];
+        for row in 0..rows {
+            for col in 0..cols {
+                let tiles = if outside[row * cols + col] {
+                    span(&ys, row) * span(&xs, col)
+                } else {
+                    0
+                };
+                blocked[row * cols + col] = tiles > 0;
+                outside_prefix[(row + 1) * (cols + 1) + col + 1] = tiles
+                    + outside_prefix[row * (cols + 1) + col + 1]
+                    + outside_prefix[(row + 1) * (cols + 1) + col]
+                    - outside_prefix[row * (cols + 1) + col];
+            }
+        }
+
+        RectilinearPolygon {
+            vertices: vertices.to_vec(),
+            xs,
+            ys,
+            cols,
+            rows,
+            blocked,
+            outside_prefix,
+        }
+    }
+
+    /// Number of outside tiles in compressed rows `r1..=r2` and columns `c1..=c2`.
+    fn outside_tiles(&self, (c1, r1): (usize, usize), (c2, r2): (usize, usize)) -> i128 {
+        let width = self.cols + 1;
+        self.outside_prefix[(r2 + 1) * width + c2 + 1]
+            - self.outside_prefix[r1 * width + c2 + 1]
+            - self.outside_prefix[(r2 + 1) * width + c1]
+            + self.outside_prefix[r1 * width + c1]
+    }
+
+    /// Returns `true` if tile `(x, y)` is on or inside the polygon.
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        self.contains_rectangle((x, y), (x, y))
+    }
+
+    /// Returns `true` if every tile of the axis-aligned rectangle with
+    /// opposite corners `a` and `b` is on or inside the polygon.
+    ///
+    /// O(log n) to locate the corners, then O(1).
+    pub fn contains_rectangle(&self, a: (i64, i64), b: (i64, i64)) -> bool {
+        if self.xs.is_empty() {
+            return false;
+        }
+        let c1 = compress(&self.xs, a.0.min(b.0));
+        let c2 = compress(&self.xs, a.0.max(b.0));
+        let r1 = compress(&self.ys, a.1.min(b.1));
+        let r2 = compress(&self.ys, a.1.max(b.1));
+        debug_assert!(c2 < self.cols && r2 < self.rows);
+        self.outside_tiles((c1, r1), (c2, r2)) == 0
+    }
+
+    /// The largest rectangle lying entirely on or inside the polygon whose
+    /// opposite corners are two of its vertices, or `None` if there are
+    /// fewer than two vertices.
+    ///
+    /// Rather than testing every pair of vertices, each vertex sweeps the
+    /// compressed columns to its right, upwards and downwards. The furthest
+    /// row reachable without leaving the polygon only shrinks as the sweep
+    /// widens, so each column needs just one binary search for its furthest
+    /// reachable vertex, and the sweep stops as soon as the column is blocked
+    /// or even the widest remaining rectangle could not beat the best so far.
+    ///
+    /// # Example
+    /// ```
+    /// use advent_of_code_2025::RectilinearPolygon;
+    /// // An L shape: the wide arm beats the tall one.
+    /// let polygon = RectilinearPolygon::new(&[(0, 0), (10, 0), (10, 4), (4, 4), (4, 8), (0, 8)]);
+    /// let best = polygon.largest_vertex_rectangle().unwrap();
+    /// assert_eq!(best.area, 55);
+    /// assert_eq!(best.corners, ((0, 0), (10, 4)));
+    /// ```
+    pub fn largest_vertex_rectangle(&self) -> Option<Rectangle> {
+        // Vertex rows in each vertex column, sorted.
+        let mut column_rows: Vec<Vec<usize>> = vec![Vec::new(); self.xs.len()];
+        for &(x, y) in &self.vertices {
+            column_rows[compress(&self.xs, x) / 2].push(compress(&self.ys, y));
+        }
+        for rows in &mut column_rows {
+            rows.sort_unstable();
+        }
+
+        // Length of the unblocked run starting at each cell, going down and up.
+        let mut down = vec![0u32; self.rows * self.cols];
+        let mut up = vec![0u32; self.rows * self.cols];
+        for col in 0..self.cols {
+            for row in (0..self.rows).rev() {
+                let index = row * self.cols + col;
+                if !self.blocked[index] {
+                    down[index] = 1 + if row + 1 < self.rows { down[index + self.cols] } else { 0 };
+                }
+            }
+            for row in 0..self.rows {
+                let index = row * self.cols + col;
+                if !self.blocked[index] {
+                    up[index] = 1 + if row > 0 { up[index - self.cols] } else { 0 };
+                }
+            }
+        }
+
+        let mut best = None;
+        for &anchor in &self.vertices {
+            self.sweep(anchor, true, &column_rows, &down, &mut best);
+            self.sweep(anchor, false, &column_rows, &up, &mut best);
+        }
+        best
+    }
+
+    /// The last (going down) or first (going up) y coordinate covered by
+    /// compressed row `row`, which must not be one of the unbounded outer rows.
+    fn row_edge(&self, row: usize, downwards: bool) -> i64 {
+        match (row % 2 == 1, downwards) {
+            (true, _) => self.ys[row / 2],
+            (false, true) => self.ys[row / 2] - 1,
+            (false, false) => self.ys[row / 2 - 1] + 1,
+        }
+    }
+
+    /// Sweeps right from `anchor`, extending rectangles down (or up) as far
+    /// as `runs` allows, and records any vertex corner that beats `best`.
+    fn sweep(
+        &self,
+        anchor: (i64, i64),
+        downwards: bool,
+        column_rows: &[Vec<usize>],
+        runs: &[u32],
+        best: &mut Option<Rectangle>,
+    ) {
+        let anchor_col = compress(&self.xs, anchor.0);
+        let anchor_row = compress(&self.ys, anchor.1);
+        let last_x = *self.xs.last().unwrap();
+
+        // Furthest row every column so far can reach; only ever shrinks.
+        let mut reach = anchor_row;
+        for col in anchor_col..self.cols {
+            let run = runs[anchor_row * self.cols + col] as usize;
+            if run == 0 {
+                break;
+            }
+            let furthest = if downwards { anchor_row + run - 1 } else { anchor_row + 1 - run };
+            reach = if col == anchor_col {
+                furthest
+            } else if downwards {
+                reach.min(furthest)
+            } else {
+                reach.max(furthest)
+            };
+
+            // Even the full remaining width at this height can't win.
+            let reach_y = self.row_edge(reach, downwards);
+            let bound = (last_x - anchor.0 + 1) as i128 * ((reach_y - anchor.1).abs() + 1) as i128;
+            if best.as_ref().is_some_and(|best: &Rectangle| bound <= best.area) {
+                break;
+            }
+
+            if col % 2 == 0 {
+                continue;
+            }
+            let rows = &column_rows[col / 2];
+            let row = if downwards {
+                rows[..rows.partition_point(|&row| row <= reach)]
+                    .last()
+                    .filter(|&&row| row >= anchor_row)
+            } else {
+                rows.get(rows.partition_point(|&row| row < reach))
+                    .filter(|&&row| row <= anchor_row)
+            };
+            if let Some(&row) = row {
+                if (col, row) == (anchor_col, anchor_row) {
+                    continue;
+                }
+                let corner = (self.xs[col / 2], self.ys[row / 2]);
+                let area =
+                    (corner.0 - anchor.0 + 1) as i128 * ((corner.1 - anchor.1).abs() + 1) as i128;
+                if best.as_ref().is_none_or(|best| area > best.area) {
+                    *best = Some(Rectangle { area, corners: (anchor, corner) });
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// How the distance between two points is measured.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Metric {
+    /// Straight-line distance, reported squared so it stays an integer.
+    /// Ordering by squared distance is the same as ordering by distance.
+    #[default]
+    Euclidean,
+    /// Sum of the absolute differences on each axis.
+    Manhattan,
+    /// Largest absolute difference on any axis.
+    Chebyshev,
+}
+
+impl Metric {
+    /// Distance between two points with the same number of dimensions, which can only
+    /// overflow if a coordinate is beyond [`Metric::max_coordinate`].
+    ///
+    /// # Example
+    /// ```
+    /// use advent_of_code_2025::spatial::Metric;
+    /// assert_eq!(Metric::Euclidean.distance(&[0, 0, 0], &[1, 2, 2]), 9);
+    /// assert_eq!(Metric::Manhattan.distance(&[0, 0, 0], &[1, -2, 2]), 5);
+    /// assert_eq!(Metric::Chebyshev.distance(&[0, 0], &[1, -3]), 3);
+    /// ```
+    pub fn distance(&self, a: &[i64], b: &[i64]) -> i64 {
+        let diffs = a.iter().zip(b).map(|(a, b)| (a - b).abs());
+        match self {
+            Metric::Euclidean => diffs.map(|d| d * d).sum(),
+            Metric::Manhattan => diffs.sum(),
+            Metric::Chebyshev => diffs.max().unwrap_or(0),
+        }
+    }
+
+    /// The largest absolute coordinate for which every [`Metric::distance`] between
+    /// points with this many dimensions fits in an `i64`.
+    ///
+    /// # Example
+    /// ```
+    /// use advent_of_code_2025::spatial::Metric;
+    /// let max = Metric::Euclidean.max_coordinate(3);
+    /// assert!(Metric::Euclidean.distance(&[max; 3], &[-max; 3]) > 0);
+    /// assert_eq!(Metric::Manhattan.max_coordinate(2), i64::MAX / 4);
+    /// ```
+    pub fn max_coordinate(&self, dimensions: usize) -> i64 {
+        // Two points can differ by up to twice the largest coordinate on each axis.
+        let axes = dimensions.max(1) as i64;
+        match self {
+            Metric::Euclidean => (i64::MAX / axes).isqrt() / 2,
+            Metric::Manhattan => i64::MAX / axes / 2,
+            Metric::Chebyshev => i64::MAX / 2,
+        }
+    }
+
+    /// The largest distance, as returned by [`Metric::distance`], that is no more than
+    /// `length` apart. For [`Metric::Euclidean`] that means squaring it.
+    ///
+    /// # Example
+    /// ```
+    /// use advent_of_code_2025::spatial::Metric;
+    /// assert_eq!(Metric::Euclidean.threshold(3.5), 12);
+    /// assert_eq!(Metric::Manhattan.threshold(3.5), 3);
+    /// ```
+    pub fn threshold(&self, length: f64) -> i64 {
+        match self {
+            Metric::Euclidean => {
+                // Squaring can round down past a length that came from a square root.
+                let squared = (length * length).floor() as i64;
+                if ((squared + 1) as f64).sqrt() <= length { squared + 1 } else { squared }
+            }
+            Metric::Manhattan | Metric::Chebyshev => length.floor() as i64,
+        }
+    }
+
+    /// How far apart a [`Metric::distance`] really is: the square root for
+    /// [`Metric::Euclidean`], otherwise the distance itself.
+    pub fn length(&self, distance: i64) -> f64 {
+        match self {
+            Metric::Euclidean => (distance as f64).sqrt(),
+            Metric::Manhattan | Metric::Chebyshev => distance as f64,
+        }
+    }
+
+    /// Smallest possible distance to any point on the far side of a
+    /// splitting plane that is `diff` away along one axis.
+    fn plane_distance(&self, diff: i64) -> i64 {
+        match self {
+            Metric::Euclidean => diff * diff,
+            Metric::Manhattan | Metric::Chebyshev => diff.abs(),
+        }
+    }
+}
+
+/// A static k-d tree over a set of points of any fixed dimension, for
+/// nearest-neighbour queries under a chosen [`Metric`].
+///
+/// The tree is stored implicitly: `nodes` holds the point indices reordered
+/// so the middle element of each sub-slice is the splitting point on the axis
+/// given by its depth, down to small leaf slices that are scanned directly.
+pub struct KdTree {
+    dimensions: usize,
+    metric: Metric,
+    // Coordinates of point `i` are `coordinates[i * dimensions..(i + 1) * dimensions]`.
+    coordinates: Vec<i64>,
+    nodes: Vec<usize>,
+}
+
+const LEAF_SIZE: usize = 8;
+
+impl KdTree {
+    /// # Panics
+    /// If the points do not all have the same number of dimensions, or a coordinate is
+    /// beyond [`Metric::max_coordinate`] so distances could overflow.
+    pub fn new(points: &[Vec<i64>], metric: Metric) -> Self {
+        let dimensions = points.first().map_or(0, |point| point.len());
+        assert!(
+            points.iter().all(|point| point.len() == dimensions),
+            "Expected every point to have {} dimensions",
+            dimensions
+        );
+        let max_coordinate = metric.max_coordinate(dimensions);
+        assert!(
+            points.iter().flatten().all(|c| (-max_coordinate..=max_coordinate).contains(c)),
+            "Expected every coordinate to be within ±{} so distances can't overflow",
+            max_coordinate
+        );
+
+        let mut tree = KdTree {
+            dimensions,
+            metric,
+            coordinates: points.concat(),
+            nodes: Vec::new(),
+        };
+        let mut nodes: Vec<usize> = (0..points.len()).collect();
+        tree.build(&mut nodes, 0);
+        tree.nodes = nodes;
+        tree
+    }
+
+    /// Coordinates of point `i`.
+    pub fn point(&self, i: usize) -> &[i64] {
+        &self.coordinates[i * self.dimensions..(i + 1) * self.dimensions]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn is_leaf(&self, nodes: &[usize]) -> bool {
+        nodes.len() <= LEAF_SIZE || self.dimensions == 0
+    }
+
+    fn build(&self, nodes: &mut [usize], depth: usize) {
+        if self.is_leaf(nodes) {
+            return;
+        }
+        let axis = depth % self.dimensions;
+        let mid = nodes.len() / 2;
+        nodes.select_nth_unstable_by_key(mid, |&i| self.point(i)[axis]);
+
+        // Everything left of `mid` is <= the split, everything right of it >=.
+        let (left, right) = nodes.split_at_mut(mid);
+        self.build(left, depth + 1);
+        self.build(&mut right[1..], depth + 1);
+    }
+
+    /// The `k` points nearest to point `query`, excluding itself, as
+    /// `(distance, index)` sorted by distance then index.
+    ///
+    /// Ties are broken by index so that asking for more neighbours always
+    /// extends the previous answer.
+    ///
+    /// # Example
+    /// ```
+    /// use advent_of_code_2025::spatial::{KdTree, Metric};
+    /// let points = vec![vec![0, 0, 0], vec![5, 0, 0], vec![1, 0, 0], vec![0, 2, 0]];
+    /// let tree = KdTree::new(&points, Metric::Euclidean);
+    /// assert_eq!(tree.nearest(0, 2), vec![(1, 2), (4, 3)]);
+    /// ```
+    pub fn nearest(&self, query: usize, k: usize) -> Vec<(i64, usize)> {
+        let mut best: BinaryHeap<(i64, usize)> = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search(query, k, &self.nodes, 0, &mut best);
+        }
+        best.into_sorted_vec()
+    }
+
+    fn search(
+        &self,
+        query: usize,
+        k: usize,
+        nodes: &[usize],
+        depth: usize,
+        best: &mut BinaryHeap<(i64, usize)>,
+    ) {
+        let query_point = self.point(query);
+        let mut consider = |index: usize| {
+            if index == query {
+                return;
+            }
+            let candidate = (self.metric.distance(query_point, self.point(index)), index);
+            if best.len() < k {
+                best.push(candidate);
+            } else if candidate < *best.peek().unwrap() {
+                best.pop();
+                best.push(candidate);
+            }
+        };
+
+        if self.is_leaf(nodes) {
+            nodes.iter().copied().for_each(consider);
+            return;
+        }
+
+        let axis = depth % self.dimensions;
+        let mid = nodes.len() / 2;
+        consider(nodes[mid]);
+
+        let diff = query_point[axis] - self.point(nodes[mid])[axis];
+        let (left, right) = (&nodes[..mid], &nodes[mid + 1..]);
+        let (near, far) = if diff < 0 { (left, right) } else { (right, left) };
+
+        self.search(query, k, near, depth + 1, best);
+        // Only cross the splitting plane if something closer (or tied) could be there.
+        if best.len() < k || self.metric.plane_distance(diff) <= best.peek().unwrap().0 {
+            self.search(query, k, far, depth + 1, best);
+        }
+    }
+}
+
+/// Each point's neighbours, fetched from the tree in growing batches.
+struct NeighbourStream {
+    fetched: Vec<(i64, usize)>,
+    requested: usize,
+    next: usize,
+}
+
+/// Iterator over every pair of points as `(distance, i, j)` with `i < j`,
+/// in increasing order of distance (ties broken by `i` then `j`).
+///
+/// Pairs are produced lazily: each point only looks as far out as the
+/// distances handed out so far, so taking the closest few pairs, or taking
+/// pairs until a graph connects, avoids materialising all n² pairs.
+pub struct ClosestPairs {
+    tree: KdTree,
+    streams: Vec<NeighbourStream>,
+    // Head of each point's stream keyed by the pair it would produce:
+    // (distance, low index, high index, owning point).
+    heap: BinaryHeap<Reverse<(i64, usize, usize, usize)>>,
+}
+
+const INITIAL_BATCH: usize = 8;
+
+impl ClosestPairs {
+    /// # Example
+    /// ```
+    /// use advent_of_code_2025::spatial::{ClosestPairs, Metric};
+    /// let points = vec![vec![0, 0], vec![10, 0], vec![1, 3]];
+    /// let pairs: Vec<_> = ClosestPairs::new(&points, Metric::Manhattan).collect();
+    /// assert_eq!(pairs, vec![(4, 0, 2), (10, 0, 1), (12, 1, 2)]);
+    /// ```
+    pub fn new(points: &[Vec<i64>], metric: Metric) -> Self {
+        let tree = KdTree::new(points, metric);
+        let mut pairs = ClosestPairs {
+            streams: Vec::with_capacity(tree.len()),
+            tree,
+            heap: BinaryHeap::new(),
+        };
+        for i in 0..pairs.tree.len() {
+            pairs.streams.push(NeighbourStream {
+                fetched: pairs.tree.nearest(i, INITIAL_BATCH),
+                requested: INITIAL_BATCH,
+                next: 0,
+            });
+            pairs.push_head(i);
+        }
+        pairs
+    }
+
+    /// Pushes the next neighbour of point `i` onto the heap, fetching a
+    /// bigger batch from the tree if the current one is used up.
+    fn push_head(&mut self, i: usize) {
+        let stream = &mut self.streams[i];
+        if stream.next == stream.fetched.len() {
+            // A short batch means every other point has already been seen.
+            if stream.fetched.len() < stream.requested {
+                return;
+            }
+            stream.requested *= 2;
+            stream.fetched = self.tree.nearest(i, stream.requested);
+            if stream.next == stream.fetched.len() {
+                return;
+            }
+        }
+
+        let (distance, j) = stream.fetched[stream.next];
+        stream.next += 1;
+        self.heap.push(Reverse((distance, i.min(j), i.max(j), i)));
+    }
+}
+
+impl Iterator for ClosestPairs {
+    type Item = (i64, usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Every pair shows up in both points' streams; only the stream of the
+        // lower-indexed point reports it.
+        while let Some(Reverse((distance, i, j, owner))) = self.heap.pop() {
+            self.push_head(owner);
+            if owner == i {
+                return Some((distance, i, j));
+            }
+        }
+        None
+    }
+}